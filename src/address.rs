@@ -0,0 +1,53 @@
+use sha3::{Digest, Keccak256};
+
+/// Returns the EIP-55 checksummed form of a hex address, with or without `0x` prefix
+pub fn to_checksum(address: &str) -> Result<String, String> {
+    let hex = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address);
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid address: {}", address));
+    }
+
+    let lower = hex.to_ascii_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+
+    // Letters whose corresponding hash nibble is >= 8 are uppercased
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    Ok(format!("0x{}", checksummed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_checksum() {
+        // Test vectors from EIP-55
+        assert_eq!(
+            to_checksum("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+        assert_eq!(
+            to_checksum("FB6916095CA1DF60BB79CE92CE3EA74C37C5D359").unwrap(),
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        );
+
+        assert!(to_checksum("0x1234").is_err());
+        assert!(to_checksum("0xZZaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+        assert!(to_checksum("").is_err());
+    }
+}
@@ -1,30 +1,97 @@
-use crate::network::SUPPORTED_NETWORKS;
+use super::project::EnvioError;
+use crate::address::to_checksum;
+use crate::network::{networks_with_traces, SUPPORTED_NETWORKS};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// How long a single explorer request may take before its lookup is given up
+const EXPLORER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum ContractSource {
+    /// An ABI given inline, as a local file or by URL, used in that order of precedence
     Abi {
         abi: Option<String>,
         url: Option<String>,
+        /// Relative to the ABI directory the [`EnvioManager`](super::EnvioManager) is set up with
+        #[serde(default)]
+        path: Option<PathBuf>,
     },
     Explorer {
         api_url: String,
+        /// Explorer API keys keyed by network id or name, so each chain can use its own explorer
+        #[serde(default)]
+        api_keys: HashMap<String, String>,
     },
     Inferred,
 }
 
+// The predicates below match exhaustively so that adding a variant forces them to be revisited
 impl ContractSource {
+    /// Returns true if the ABI is fetched from a block explorer
     pub fn is_explorer(&self) -> bool {
-        matches!(self, ContractSource::Explorer { .. })
+        match self {
+            ContractSource::Explorer { .. } => true,
+            ContractSource::Abi { .. } | ContractSource::Inferred => false,
+        }
     }
 
+    /// Returns true if the ABI is provided inline or by URL
     pub fn is_abi(&self) -> bool {
-        matches!(self, ContractSource::Abi { .. })
+        match self {
+            ContractSource::Abi { .. } => true,
+            ContractSource::Explorer { .. } | ContractSource::Inferred => false,
+        }
     }
 
+    /// Returns true if envio should infer the ABI from the address and network
     pub fn is_inferred(&self) -> bool {
-        matches!(self, ContractSource::Inferred)
+        match self {
+            ContractSource::Inferred => true,
+            ContractSource::Abi { .. } | ContractSource::Explorer { .. } => false,
+        }
+    }
+
+    /// Returns the inline ABI and ABI URL of an `Abi` source
+    pub fn as_abi(&self) -> Option<(&Option<String>, &Option<String>)> {
+        match self {
+            ContractSource::Abi { abi, url, .. } => Some((abi, url)),
+            ContractSource::Explorer { .. } | ContractSource::Inferred => None,
+        }
+    }
+
+    /// Returns the explorer API key configured for the network of `deployment`
+    ///
+    /// Keys for the network as written take precedence over keys for its chain id, which take
+    /// precedence over keys for its network name.
+    pub fn explorer_api_key(&self, deployment: &ContractDeployment) -> Option<&str> {
+        let ContractSource::Explorer { api_keys, .. } = self else {
+            return None;
+        };
+        let chain_id = deployment.resolve_network_to_number();
+        let network_name = chain_id
+            .parse::<u64>()
+            .ok()
+            .and_then(|id| SUPPORTED_NETWORKS.get(&id))
+            .map(|network| network.name.as_str());
+
+        // Sorted, so keys differing only in case resolve the same way every time
+        let mut keys: Vec<(&String, &String)> = api_keys.iter().collect();
+        keys.sort();
+        [
+            Some(deployment.network_id.as_str()),
+            Some(chain_id.as_str()),
+            network_name,
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| {
+            keys.iter()
+                .find(|(network, _)| network.eq_ignore_ascii_case(candidate))
+                .map(|(_, key)| key.as_str())
+        })
     }
 
     pub fn get_abi(&self) -> Option<String> {
@@ -40,22 +107,40 @@ impl ContractSource {
             _ => None,
         }
     }
+
+    pub fn get_path(&self) -> Option<PathBuf> {
+        match self {
+            ContractSource::Abi { path, .. } => path.clone(),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ContractDeployment {
     pub network_id: String,
+    /// Address of the contract the ABI belongs to, the implementation behind `proxy_address`
     pub address: String,
     pub rpc_url: String,
+    /// Proxy forwarding to `address`. Events are emitted by the proxy, so it is what envio watches
     pub proxy_address: Option<String>,
     pub start_block: Option<u64>,
+    /// Last block to index, inclusive. `None` keeps following the chain head
+    #[serde(default)]
+    pub end_block: Option<u64>,
+    /// RPC endpoints envio falls back to when its primary data source is unavailable
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ContractConfig {
     pub name: String,
     pub source: ContractSource,
     pub deployments: Vec<ContractDeployment>,
+    /// Whether the indexer needs trace data, which only some networks provide
+    #[serde(default)]
+    pub requires_traces: bool,
 }
 
 impl ContractConfig {
@@ -64,6 +149,7 @@ impl ContractConfig {
             name,
             source,
             deployments,
+            requires_traces: false,
         }
     }
 
@@ -81,11 +167,287 @@ impl ContractConfig {
             rpc_url,
             proxy_address,
             start_block,
+            end_block: None,
+            fallback_rpc_urls: Vec::new(),
         });
     }
+
+    /// The first deployment on `network_id`, given by chain id or by network name
+    pub fn deployment_for_network(&self, network_id: &str) -> Option<&ContractDeployment> {
+        let network_id = resolve_network_id(network_id);
+        self.deployments
+            .iter()
+            .find(|deployment| deployment.resolve_network_to_number() == network_id)
+    }
+
+    /// Removes deployments repeating the same network and indexed address (case-insensitive).
+    ///
+    /// Returns how many were removed, or an error if two deployments of the same indexed address
+    /// disagree on their implementation, proxy address or block range.
+    pub fn dedup_deployments(&mut self) -> Result<usize, String> {
+        self.check_duplicate_deployments()?;
+
+        let before = self.deployments.len();
+        let mut kept: Vec<ContractDeployment> = Vec::with_capacity(before);
+        for deployment in self.deployments.drain(..) {
+            if !kept.iter().any(|k| k.is_same_contract(&deployment)) {
+                kept.push(deployment);
+            }
+        }
+        self.deployments = kept;
+        Ok(before - self.deployments.len())
+    }
+
+    /// Fails if the same address is deployed twice on a network with different metadata
+    fn check_duplicate_deployments(&self) -> Result<(), String> {
+        for (i, deployment) in self.deployments.iter().enumerate() {
+            let conflict = self.deployments[i + 1..].iter().find(|other| {
+                deployment.is_same_contract(other)
+                    && (deployment.start_block != other.start_block
+                        || deployment.end_block != other.end_block
+                        || !deployment.address.eq_ignore_ascii_case(&other.address)
+                        || deployment.proxy_address.is_some() != other.proxy_address.is_some())
+            });
+            if conflict.is_some() {
+                return Err(format!(
+                    "Contract {} lists address {} on network {} twice with conflicting start block, end block, implementation or proxy address",
+                    self.name, deployment.indexed_address(), deployment.network_id
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Groups deployments by network, in order of first appearance
+    ///
+    /// envio keeps every address on a network under a single contract entry.
+    pub fn deployments_by_network(&self) -> Vec<NetworkDeployments> {
+        let mut groups: Vec<NetworkDeployments> = Vec::new();
+        for deployment in &self.deployments {
+            let network_id = deployment.resolve_network_to_number();
+            match groups.iter_mut().find(|g| g.network_id == network_id) {
+                Some(group) => {
+                    group
+                        .addresses
+                        .push(deployment.indexed_address().to_string());
+                    group.start_block = match (group.start_block, deployment.start_block) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        _ => None,
+                    };
+                }
+                None => groups.push(NetworkDeployments {
+                    network_id,
+                    rpc_url: deployment.rpc_url.clone(),
+                    addresses: vec![deployment.indexed_address().to_string()],
+                    start_block: deployment.start_block,
+                }),
+            }
+        }
+        groups
+    }
+
+    /// Reorders deployments so that those on the same network are adjacent
+    pub fn group_deployments(&mut self) {
+        let order: Vec<String> = self
+            .deployments_by_network()
+            .into_iter()
+            .map(|g| g.network_id)
+            .collect();
+        self.deployments.sort_by_key(|d| {
+            let network_id = d.resolve_network_to_number();
+            order.iter().position(|id| *id == network_id)
+        });
+    }
+}
+
+/// The parts of an envio `config.yaml` that map onto an [`IndexerConfig`]
+#[derive(Deserialize)]
+struct EnvioConfigFile {
+    name: String,
+    #[serde(default)]
+    contracts: Vec<EnvioConfigContract>,
+    #[serde(default)]
+    networks: Vec<EnvioConfigNetwork>,
+}
+
+#[derive(Deserialize)]
+struct EnvioConfigNetwork {
+    id: u64,
+    #[serde(default)]
+    start_block: Option<u64>,
+    #[serde(default)]
+    end_block: Option<u64>,
+    #[serde(default)]
+    rpc_config: Option<EnvioConfigRpc>,
+    #[serde(default)]
+    contracts: Vec<EnvioConfigContract>,
+}
+
+#[derive(Deserialize)]
+struct EnvioConfigRpc {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct EnvioConfigContract {
+    name: String,
+    #[serde(default)]
+    abi_file_path: Option<String>,
+    #[serde(default)]
+    address: EnvioConfigAddress,
+}
+
+/// envio accepts a single address or a list
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EnvioConfigAddress {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for EnvioConfigAddress {
+    fn default() -> Self {
+        EnvioConfigAddress::Many(Vec::new())
+    }
+}
+
+impl EnvioConfigAddress {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            EnvioConfigAddress::One(address) => vec![address],
+            EnvioConfigAddress::Many(addresses) => addresses,
+        }
+    }
+}
+
+/// Reconstructs an [`IndexerConfig`] from the contents of an envio `config.yaml`.
+///
+/// Each address of a contract on a network becomes one deployment. Contracts with an
+/// `abi_file_path` get an `Abi` source pointing at that (project-relative) file, the rest are
+/// `Inferred`. A network without an `rpc_config` yields deployments with an empty `rpc_url`.
+pub fn parse_envio_config(yaml: &str) -> Result<IndexerConfig, EnvioError> {
+    let file: EnvioConfigFile = serde_yaml::from_str(yaml)?;
+
+    let mut contracts: Vec<ContractConfig> = Vec::new();
+    for network in file.networks {
+        let rpc_url = network.rpc_config.map(|rpc| rpc.url).unwrap_or_default();
+
+        for contract in network.contracts {
+            // The ABI may be declared on the network entry or the global contract entry
+            let abi_file_path = contract.abi_file_path.or_else(|| {
+                file.contracts
+                    .iter()
+                    .find(|c| c.name == contract.name)
+                    .and_then(|c| c.abi_file_path.clone())
+            });
+
+            let config = match contracts.iter_mut().find(|c| c.name == contract.name) {
+                Some(config) => config,
+                None => {
+                    let source = match abi_file_path {
+                        Some(path) => ContractSource::Abi {
+                            abi: None,
+                            url: None,
+                            path: Some(PathBuf::from(path)),
+                        },
+                        None => ContractSource::Inferred,
+                    };
+                    contracts.push(ContractConfig::new(contract.name, source, Vec::new()));
+                    contracts.last_mut().expect("contract was just pushed")
+                }
+            };
+
+            for address in contract.address.into_vec() {
+                config.deployments.push(ContractDeployment {
+                    end_block: network.end_block,
+                    ..ContractDeployment::new(
+                        network.id.to_string(),
+                        address,
+                        rpc_url.clone(),
+                        None,
+                        network.start_block,
+                    )
+                });
+            }
+        }
+    }
+
+    Ok(IndexerConfig::new(file.name, contracts))
+}
+
+/// Transport of an RPC endpoint, envio configures HTTP and WebSocket endpoints differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcScheme {
+    Http,
+    WebSocket,
+}
+
+impl RpcScheme {
+    /// Scheme of `url`, rejecting anything but `http(s)://` and `ws(s)://` URLs with a host
+    pub fn of(url: &str) -> Result<Self, String> {
+        let url = url.trim();
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| format!("RPC URL {:?} has no scheme", url))?;
+        let scheme = match scheme.to_ascii_lowercase().as_str() {
+            "http" | "https" => RpcScheme::Http,
+            "ws" | "wss" => RpcScheme::WebSocket,
+            other => {
+                return Err(format!(
+                    "RPC URL {:?} has unsupported scheme {:?}, use http(s) or ws(s)",
+                    url, other
+                ))
+            }
+        };
+        if rest.is_empty() || rest.starts_with('/') {
+            return Err(format!("RPC URL {:?} has no host", url));
+        }
+        Ok(scheme)
+    }
+}
+
+/// Serialization format of an exported [`IndexerConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+}
+
+/// Distinct RPC URLs given for the same network
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcConflict {
+    pub network_id: String,
+    /// URLs in order of first appearance, the first one is used
+    pub rpc_urls: Vec<String>,
+}
+
+impl std::fmt::Display for RpcConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Network {} has conflicting RPC URLs ({}), using {}",
+            self.network_id,
+            self.rpc_urls.join(", "),
+            self.rpc_urls[0]
+        )
+    }
+}
+
+/// All addresses of a contract on one network
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkDeployments {
+    pub network_id: String,
+    pub rpc_url: String,
+    pub addresses: Vec<String>,
+    /// Earliest start block of the grouped deployments, `None` if any starts from genesis
+    pub start_block: Option<u64>,
 }
 
 impl ContractDeployment {
+    pub fn builder() -> ContractDeploymentBuilder {
+        ContractDeploymentBuilder::default()
+    }
+
     pub fn new(
         network_id: String,
         address: String,
@@ -99,24 +461,100 @@ impl ContractDeployment {
             rpc_url,
             proxy_address,
             start_block,
+            end_block: None,
+            fallback_rpc_urls: Vec::new(),
         }
     }
 
+    /// Returns true if both deployments index the same address on the same network
+    ///
+    /// Proxied deployments are compared by their proxy, which is the address envio indexes.
+    pub fn is_same_contract(&self, other: &ContractDeployment) -> bool {
+        self.resolve_network_to_number() == other.resolve_network_to_number()
+            && self
+                .indexed_address()
+                .eq_ignore_ascii_case(other.indexed_address())
+    }
+
     pub fn resolve_network_to_number(&self) -> String {
-        // If it's already a number, return as-is
-        if let Ok(id) = self.network_id.parse::<u64>() {
-            return id.to_string();
+        resolve_network_id(&self.network_id)
+    }
+
+    /// Resolves the block to start indexing from, caching it in `start_block`
+    ///
+    /// When unset, the contract creation block is looked up through the explorer API, with
+    /// `api_key` if given. Without an explorer, or if the explorer has no creation record,
+    /// indexing starts from block 0.
+    pub async fn resolve_start_block(
+        &mut self,
+        explorer_api_url: Option<&str>,
+        api_key: Option<&str>,
+    ) -> Result<u64, EnvioError> {
+        if let Some(start_block) = self.start_block {
+            return Ok(start_block);
         }
+        let Some(api_url) = explorer_api_url else {
+            return Ok(0);
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(EXPLORER_TIMEOUT)
+            .build()?;
+        let request = |params: &[(&str, &str)]| {
+            let request = client.get(api_url).query(params);
+            match api_key {
+                Some(api_key) => request.query(&[("apikey", api_key)]),
+                None => request,
+            }
+        };
+        let creation: serde_json::Value = request(&[
+            ("module", "contract"),
+            ("action", "getcontractcreation"),
+            // Indexing starts where the proxy was created, not the implementation
+            ("contractaddresses", self.indexed_address()),
+        ])
+        .send()
+        .await?
+        .json()
+        .await?;
+        let Some(record) = creation["result"].get(0) else {
+            return Ok(0);
+        };
+
+        // Not every explorer includes the block number, fall back to the creation transaction
+        let block = match record["blockNumber"].as_str() {
+            Some(block) => block.parse::<u64>().ok(),
+            None => match record["txHash"].as_str() {
+                Some(tx_hash) => {
+                    let tx: serde_json::Value = request(&[
+                        ("module", "proxy"),
+                        ("action", "eth_getTransactionByHash"),
+                        ("txhash", tx_hash),
+                    ])
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+                    tx["result"]["blockNumber"].as_str().and_then(|block| {
+                        u64::from_str_radix(block.trim_start_matches("0x"), 16).ok()
+                    })
+                }
+                None => None,
+            },
+        };
 
-        // Look up network ID from supported networks
-        for (id, info) in SUPPORTED_NETWORKS.iter() {
-            if info.name.to_lowercase() == self.network_id.to_lowercase() {
-                return id.to_string();
+        match block {
+            Some(block) => {
+                self.start_block = Some(block);
+                Ok(block)
             }
+            None => Ok(0),
         }
+    }
 
-        // If not found, return original value
-        self.network_id.clone()
+    /// Address envio watches for events, the proxy if there is one
+    pub fn indexed_address(&self) -> &str {
+        self.proxy_address.as_deref().unwrap_or(&self.address)
     }
 
     pub fn resolve_network_to_string(&self) -> String {
@@ -133,15 +571,125 @@ impl ContractDeployment {
     }
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// Chain id of a network given by id or by name, or `network` itself if it is unknown
+pub fn resolve_network_id(network: &str) -> String {
+    // If it's already a number, return as-is
+    if let Ok(id) = network.parse::<u64>() {
+        return id.to_string();
+    }
+
+    // Look up network ID from supported networks
+    for (id, info) in SUPPORTED_NETWORKS.iter() {
+        if info.name.to_lowercase() == network.to_lowercase() {
+            return id.to_string();
+        }
+    }
+
+    // If not found, return original value
+    network.to_string()
+}
+
+/// Builder for [`ContractDeployment`] that avoids mixing up its positional arguments
+#[derive(Default, Debug, Clone)]
+pub struct ContractDeploymentBuilder {
+    network_id: Option<String>,
+    address: Option<String>,
+    rpc_url: Option<String>,
+    proxy_address: Option<String>,
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+    fallback_rpc_urls: Vec<String>,
+}
+
+impl ContractDeploymentBuilder {
+    pub fn network_id(mut self, network_id: impl Into<String>) -> Self {
+        self.network_id = Some(network_id.into());
+        self
+    }
+
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.address = Some(address.into());
+        self
+    }
+
+    pub fn rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = Some(rpc_url.into());
+        self
+    }
+
+    pub fn proxy_address(mut self, proxy_address: impl Into<String>) -> Self {
+        self.proxy_address = Some(proxy_address.into());
+        self
+    }
+
+    pub fn start_block(mut self, start_block: u64) -> Self {
+        self.start_block = Some(start_block);
+        self
+    }
+
+    pub fn end_block(mut self, end_block: u64) -> Self {
+        self.end_block = Some(end_block);
+        self
+    }
+
+    /// Adds an RPC endpoint to fall back to, may be called repeatedly
+    pub fn fallback_rpc_url(mut self, fallback_rpc_url: impl Into<String>) -> Self {
+        self.fallback_rpc_urls.push(fallback_rpc_url.into());
+        self
+    }
+
+    /// Builds the deployment, failing if `network_id`, `address` or `rpc_url` is missing
+    pub fn build(self) -> Result<ContractDeployment, String> {
+        let required = |value: Option<String>, field: &str| {
+            value
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("Contract deployment is missing {}", field))
+        };
+
+        Ok(ContractDeployment {
+            network_id: required(self.network_id, "network_id")?,
+            address: required(self.address, "address")?,
+            rpc_url: required(self.rpc_url, "rpc_url")?,
+            proxy_address: self.proxy_address,
+            start_block: self.start_block,
+            end_block: self.end_block,
+            fallback_rpc_urls: self.fallback_rpc_urls,
+        })
+    }
+}
+
+/// Blockchain ecosystem the indexed contracts live in
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Ecosystem {
+    #[default]
+    Evm,
+    Fuel,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct IndexerConfig {
     pub name: String,
     pub contracts: Vec<ContractConfig>,
+    /// Spawn without contracts whose ABI can't be fetched instead of failing
+    #[serde(default)]
+    pub skip_failed_contracts: bool,
+    #[serde(default)]
+    pub ecosystem: Ecosystem,
 }
 
 impl IndexerConfig {
     pub fn new(name: String, contracts: Vec<ContractConfig>) -> Self {
-        Self { name, contracts }
+        Self {
+            name,
+            contracts,
+            skip_failed_contracts: false,
+            ecosystem: Ecosystem::Evm,
+        }
+    }
+
+    /// The contract named `name`
+    pub fn contract(&self, name: &str) -> Option<&ContractConfig> {
+        self.contracts.iter().find(|contract| contract.name == name)
     }
 
     pub fn validate(&self) -> Result<(), String> {
@@ -157,18 +705,487 @@ impl IndexerConfig {
             if contract.deployments.is_empty() {
                 return Err(format!("Contract {} has no deployments", contract.name));
             }
+
+            // Exact duplicates are dropped by `dedup_deployments`, conflicting ones are an error
+            contract.check_duplicate_deployments()?;
+
+            if let ContractSource::Abi { abi, url, path } = &contract.source {
+                if abi.is_none() && url.is_none() && path.is_none() {
+                    return Err(format!(
+                        "Contract {} has an ABI source without an inline ABI, URL or path",
+                        contract.name
+                    ));
+                }
+                if let Some(path) = path {
+                    if !is_contained_path(path) {
+                        return Err(format!(
+                            "Contract {} has ABI path {} that is not relative to the ABI directory",
+                            contract.name,
+                            path.display()
+                        ));
+                    }
+                }
+                // The inline ABI takes precedence, so a broken one is never fallen back from
+                if let Some(abi) = abi {
+                    if abi.trim().is_empty() {
+                        return Err(format!(
+                            "Contract {} has an empty inline ABI",
+                            contract.name
+                        ));
+                    }
+                    serde_json::from_str::<serde_json::Value>(abi).map_err(|e| {
+                        format!(
+                            "Contract {} has an inline ABI that is not valid JSON: {}",
+                            contract.name, e
+                        )
+                    })?;
+                }
+            }
+
+            if self.ecosystem == Ecosystem::Fuel {
+                self.validate_fuel_contract(contract)?;
+            } else {
+                for deployment in &contract.deployments {
+                    let addresses =
+                        std::iter::once(&deployment.address).chain(&deployment.proxy_address);
+                    for address in addresses {
+                        to_checksum(address)
+                            .map_err(|e| format!("Contract {}: {}", contract.name, e))?;
+                    }
+
+                    if let Some(proxy_address) = &deployment.proxy_address {
+                        if proxy_address.eq_ignore_ascii_case(&deployment.address) {
+                            return Err(format!(
+                                "Contract {} on network {} uses its own address {} as proxy address",
+                                contract.name, deployment.network_id, proxy_address
+                            ));
+                        }
+                        // envio would infer the ABI of the proxy rather than of the implementation
+                        if contract.source.is_inferred() {
+                            return Err(format!(
+                                "Contract {} is deployed behind proxy {} on network {}, which needs \
+                                 the implementation ABI instead of an inferred one",
+                                contract.name, proxy_address, deployment.network_id
+                            ));
+                        }
+                    }
+                }
+            }
+
+            for deployment in &contract.deployments {
+                if let (Some(start), Some(end)) = (deployment.start_block, deployment.end_block) {
+                    if end < start {
+                        return Err(format!(
+                            "Contract {} on network {} has end block {} before start block {}",
+                            contract.name, deployment.network_id, end, start
+                        ));
+                    }
+                }
+
+                // Fuel networks are synced from HyperFuel, envio takes no RPC for them
+                if self.ecosystem == Ecosystem::Fuel {
+                    continue;
+                }
+
+                if !deployment.rpc_url.trim().is_empty() {
+                    let scheme = RpcScheme::of(&deployment.rpc_url).map_err(|e| {
+                        format!(
+                            "Contract {} on network {} has an invalid RPC URL: {}",
+                            contract.name, deployment.network_id, e
+                        )
+                    })?;
+                    // Without HyperSync envio syncs from this URL, which it only takes over HTTP
+                    let has_hypersync = deployment
+                        .resolve_network_to_number()
+                        .parse::<u64>()
+                        .is_ok_and(|id| SUPPORTED_NETWORKS.contains_key(&id));
+                    if scheme == RpcScheme::WebSocket && !has_hypersync {
+                        return Err(format!(
+                            "Contract {} on network {} is synced from its RPC URL, which needs to \
+                             be an HTTP URL instead of {}",
+                            contract.name, deployment.network_id, deployment.rpc_url
+                        ));
+                    }
+                }
+
+                let mut http_fallbacks = 0;
+                let mut ws_fallbacks = 0;
+                for url in &deployment.fallback_rpc_urls {
+                    match RpcScheme::of(url) {
+                        Ok(RpcScheme::Http) => http_fallbacks += 1,
+                        Ok(RpcScheme::WebSocket) => ws_fallbacks += 1,
+                        Err(e) => {
+                            return Err(format!(
+                                "Contract {} on network {} has an invalid fallback RPC URL: {}",
+                                contract.name, deployment.network_id, e
+                            ))
+                        }
+                    }
+                }
+                // envio attaches a WebSocket endpoint to an HTTP one
+                if ws_fallbacks > http_fallbacks {
+                    return Err(format!(
+                        "Contract {} on network {} has more WebSocket than HTTP fallback RPC URLs, \
+                         each WebSocket URL needs an HTTP URL to pair with",
+                        contract.name, deployment.network_id
+                    ));
+                }
+            }
+
+            // envio can only infer an ABI on a supported network
+            if contract.source.is_inferred() {
+                for deployment in &contract.deployments {
+                    let is_supported = deployment
+                        .resolve_network_to_number()
+                        .parse::<u64>()
+                        .is_ok_and(|id| SUPPORTED_NETWORKS.contains_key(&id));
+                    if !is_supported {
+                        return Err(format!(
+                            "Contract {} uses an inferred ABI on unsupported network {}",
+                            contract.name, deployment.network_id
+                        ));
+                    }
+                }
+            }
+
+            if contract.requires_traces {
+                let with_traces = networks_with_traces();
+                for deployment in &contract.deployments {
+                    let network_id = deployment.resolve_network_to_number().parse::<u64>().ok();
+                    if !with_traces.iter().any(|n| Some(n.network_id) == network_id) {
+                        let network = network_id
+                            .and_then(|id| SUPPORTED_NETWORKS.get(&id))
+                            .map_or(deployment.network_id.as_str(), |n| n.name.as_str());
+                        return Err(format!(
+                            "Contract {} requires traces, which network {} does not support",
+                            contract.name, network
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Contract names key the ABI files and env prefixes, so they must be unique.
+        // The same contract on several networks belongs in one `ContractConfig`.
+        let mut seen = std::collections::HashSet::new();
+        let mut duplicates = Vec::new();
+        for contract in &self.contracts {
+            if !seen.insert(contract.name.as_str()) && !duplicates.contains(&contract.name) {
+                duplicates.push(contract.name.clone());
+            }
+        }
+        if !duplicates.is_empty() {
+            return Err(format!(
+                "Duplicate contract names: {}. Use multiple deployments under one contract instead",
+                duplicates.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks what envio supports for Fuel contracts, which differ from EVM ones in their
+    /// 32-byte addresses and need a local ABI
+    fn validate_fuel_contract(&self, contract: &ContractConfig) -> Result<(), String> {
+        if !contract.source.is_abi() {
+            return Err(format!(
+                "Fuel contract {} needs an ABI source, envio can't fetch Fuel ABIs",
+                contract.name
+            ));
+        }
+        if contract.requires_traces {
+            return Err(format!(
+                "Fuel contract {} requires traces, which are only available on EVM networks",
+                contract.name
+            ));
+        }
+        for deployment in &contract.deployments {
+            if deployment.proxy_address.is_some() {
+                return Err(format!(
+                    "Fuel contract {} has a proxy address, proxies are only supported on EVM",
+                    contract.name
+                ));
+            }
+            let is_b256 = deployment
+                .address
+                .strip_prefix("0x")
+                .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+            if !is_b256 {
+                return Err(format!(
+                    "Fuel contract {} has invalid address {}, expected 0x followed by 64 hex digits",
+                    contract.name, deployment.address
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the config, e.g. to store it alongside the indexer's other configuration
+    pub fn export(&self, format: ConfigFormat) -> Result<String, String> {
+        match format {
+            ConfigFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Failed to serialize config as JSON: {}", e)),
+            ConfigFormat::Yaml => serde_yaml::to_string(self)
+                .map_err(|e| format!("Failed to serialize config as YAML: {}", e)),
         }
+    }
 
+    /// Parses a config produced by [`Self::export`]
+    pub fn import(input: &str, format: ConfigFormat) -> Result<Self, String> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(input)
+                .map_err(|e| format!("Failed to parse JSON config: {}", e)),
+            ConfigFormat::Yaml => serde_yaml::from_str(input)
+                .map_err(|e| format!("Failed to parse YAML config: {}", e)),
+        }
+    }
+
+    /// Rewrites every deployment and proxy address in its EIP-55 checksummed form
+    ///
+    /// Fuel addresses have no checksummed form and are left as they are.
+    pub fn normalize_addresses(&mut self) -> Result<(), String> {
+        if self.ecosystem == Ecosystem::Fuel {
+            return Ok(());
+        }
+        for contract in &mut self.contracts {
+            for deployment in &mut contract.deployments {
+                deployment.address = to_checksum(&deployment.address)
+                    .map_err(|e| format!("Contract {}: {}", contract.name, e))?;
+                if let Some(proxy_address) = &deployment.proxy_address {
+                    deployment.proxy_address = Some(
+                        to_checksum(proxy_address)
+                            .map_err(|e| format!("Contract {}: {}", contract.name, e))?,
+                    );
+                }
+            }
+        }
         Ok(())
     }
+
+    /// Removes duplicate deployments from every contract, see [`ContractConfig::dedup_deployments`]
+    pub fn dedup_deployments(&mut self) -> Result<usize, String> {
+        let mut removed = 0;
+        for contract in &mut self.contracts {
+            removed += contract.dedup_deployments()?;
+        }
+        Ok(removed)
+    }
+
+    /// Looks up the creation block of explorer-sourced deployments without a start block
+    ///
+    /// Returns a warning for each lookup that failed, those deployments index from genesis.
+    pub async fn resolve_start_blocks(&mut self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for contract in &mut self.contracts {
+            let ContractSource::Explorer { api_url, .. } = &contract.source else {
+                continue;
+            };
+            for deployment in &mut contract.deployments {
+                if deployment.start_block.is_some() {
+                    continue;
+                }
+                let api_key = contract.source.explorer_api_key(deployment);
+                if let Err(e) = deployment.resolve_start_block(Some(api_url), api_key).await {
+                    warnings.push(format!(
+                        "Failed to look up the creation block of {} on network {}: {}",
+                        contract.name, deployment.network_id, e
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Block range to index on each network, across all contracts
+    ///
+    /// envio configures blocks per network, so the range covers every deployment on it: the
+    /// earliest start and the latest end, each `None` if any deployment is unbounded.
+    pub fn block_ranges(&self) -> Vec<(String, Option<u64>, Option<u64>)> {
+        let mut ranges: Vec<(String, Option<u64>, Option<u64>)> = Vec::new();
+        for deployment in self.contracts.iter().flat_map(|c| &c.deployments) {
+            let network_id = deployment.resolve_network_to_number();
+            match ranges.iter_mut().find(|(id, _, _)| *id == network_id) {
+                Some((_, start, end)) => {
+                    *start = start.zip(deployment.start_block).map(|(a, b)| a.min(b));
+                    *end = end.zip(deployment.end_block).map(|(a, b)| a.max(b));
+                }
+                None => ranges.push((network_id, deployment.start_block, deployment.end_block)),
+            }
+        }
+        ranges
+    }
+
+    /// Addresses envio watches for each contract on each network, as `(network, contract, addresses)`
+    pub fn indexed_addresses(&self) -> Vec<(String, String, Vec<String>)> {
+        let mut by_network: Vec<(String, String, Vec<String>)> = Vec::new();
+        for contract in &self.contracts {
+            for deployment in &contract.deployments {
+                let network_id = deployment.resolve_network_to_number();
+                let address = deployment.indexed_address().to_string();
+                match by_network
+                    .iter_mut()
+                    .find(|(id, name, _)| *id == network_id && *name == contract.name)
+                {
+                    Some((_, _, addresses)) => addresses.push(address),
+                    None => by_network.push((network_id, contract.name.clone(), vec![address])),
+                }
+            }
+        }
+        by_network
+    }
+
+    /// RPC URL of each network, the first one given by its deployments
+    pub fn rpc_urls(&self) -> Vec<(String, String)> {
+        let mut by_network: Vec<(String, String)> = Vec::new();
+        for deployment in self.contracts.iter().flat_map(|c| &c.deployments) {
+            let rpc_url = deployment.rpc_url.trim();
+            let network_id = deployment.resolve_network_to_number();
+            if !rpc_url.is_empty() && !by_network.iter().any(|(id, _)| *id == network_id) {
+                by_network.push((network_id, rpc_url.to_string()));
+            }
+        }
+        by_network
+    }
+
+    /// Fallback RPC URLs of each network, merged across its deployments in order of appearance
+    pub fn fallback_rpc_urls(&self) -> Vec<(String, Vec<String>)> {
+        let mut by_network: Vec<(String, Vec<String>)> = Vec::new();
+        for deployment in self.contracts.iter().flat_map(|c| &c.deployments) {
+            if deployment.fallback_rpc_urls.is_empty() {
+                continue;
+            }
+            let network_id = deployment.resolve_network_to_number();
+            let index = match by_network.iter().position(|(id, _)| *id == network_id) {
+                Some(index) => index,
+                None => {
+                    by_network.push((network_id, Vec::new()));
+                    by_network.len() - 1
+                }
+            };
+            let urls = &mut by_network[index].1;
+            for url in &deployment.fallback_rpc_urls {
+                let url = url.trim();
+                if !urls.iter().any(|u| u == url) {
+                    urls.push(url.to_string());
+                }
+            }
+        }
+        by_network
+    }
+
+    /// Networks whose deployments specify more than one distinct RPC URL
+    pub fn rpc_conflicts(&self) -> Vec<RpcConflict> {
+        let mut by_network: Vec<RpcConflict> = Vec::new();
+        let deployments = self.contracts.iter().flat_map(|c| &c.deployments);
+        for deployment in deployments {
+            let url = deployment.rpc_url.trim();
+            if url.is_empty() {
+                continue;
+            }
+            let network_id = deployment.resolve_network_to_number();
+            match by_network.iter_mut().find(|c| c.network_id == network_id) {
+                Some(entry) if !entry.rpc_urls.iter().any(|u| u == url) => {
+                    entry.rpc_urls.push(url.to_string())
+                }
+                Some(_) => {}
+                None => by_network.push(RpcConflict {
+                    network_id,
+                    rpc_urls: vec![url.to_string()],
+                }),
+            }
+        }
+        by_network.retain(|c| c.rpc_urls.len() > 1);
+        by_network
+    }
+
+    /// Non-fatal problems that `validate` lets through
+    pub fn validation_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for contract in &self.contracts {
+            for deployment in &contract.deployments {
+                if deployment.start_block.is_none() {
+                    warnings.push(format!(
+                        "Contract {} on network {} has no start block, indexing from genesis",
+                        contract.name, deployment.network_id
+                    ));
+                }
+            }
+        }
+
+        warnings.extend(self.rpc_conflicts().iter().map(ToString::to_string));
+        warnings
+    }
+
+    /// Gives every deployment on a network the same RPC URL, so envio gets one per chain.
+    ///
+    /// The first non-empty URL seen for a network wins. Returns the conflicts that were resolved.
+    pub fn normalize_rpc_urls(&mut self) -> Vec<RpcConflict> {
+        let conflicts = self.rpc_conflicts();
+
+        let mut selected: Vec<(String, String)> = Vec::new();
+        for deployment in self.contracts.iter().flat_map(|c| &c.deployments) {
+            let network_id = deployment.resolve_network_to_number();
+            if !deployment.rpc_url.trim().is_empty()
+                && !selected.iter().any(|(id, _)| *id == network_id)
+            {
+                selected.push((network_id, deployment.rpc_url.trim().to_string()));
+            }
+        }
+
+        for deployment in self.contracts.iter_mut().flat_map(|c| &mut c.deployments) {
+            let network_id = deployment.resolve_network_to_number();
+            if let Some((_, url)) = selected.iter().find(|(id, _)| *id == network_id) {
+                deployment.rpc_url = url.clone();
+            }
+        }
+
+        conflicts
+    }
+
+    /// Merges another config into this one.
+    ///
+    /// Contracts are matched by name. New contracts are appended, and new deployments
+    /// of an existing contract are appended to it unless a deployment with the same
+    /// network and address is already present.
+    pub fn merge(&mut self, other: IndexerConfig) {
+        for contract in other.contracts {
+            match self.contracts.iter_mut().find(|c| c.name == contract.name) {
+                Some(existing) => {
+                    for deployment in contract.deployments {
+                        let duplicate = existing
+                            .deployments
+                            .iter()
+                            .any(|d| d.is_same_contract(&deployment));
+                        if !duplicate {
+                            existing.deployments.push(deployment);
+                        }
+                    }
+                }
+                None => self.contracts.push(contract),
+            }
+        }
+    }
+}
+
+/// JSON Schema of [`IndexerConfig`], for generating submission forms
+pub fn indexer_config_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(IndexerConfig))
+        .expect("JSON Schema is always serializable")
+}
+
+/// Returns true if `path` is relative and doesn't climb out of the directory it is joined to
+pub(crate) fn is_contained_path(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test_utils::{
-        create_test_contract, create_test_explorer_contract, generate_multi_address_contract,
-        generate_multi_chain_contract, generate_random_contract_config,
+        create_test_contract, create_test_explorer_contract, create_test_inferred_contract,
+        generate_multi_address_contract, generate_multi_chain_contract,
+        generate_random_contract_config,
     };
 
     #[test]
@@ -254,4 +1271,913 @@ mod tests {
             .validate()
             .is_ok());
     }
+
+    #[test]
+    fn test_indexer_config_schema() {
+        let schema = indexer_config_schema();
+        assert_eq!(schema["properties"]["contracts"]["type"], "array");
+
+        let source = &schema["definitions"]["ContractSource"];
+        let source = serde_json::to_string(source).unwrap();
+        assert!(source.contains("Abi"));
+        assert!(source.contains("Explorer"));
+        assert!(source.contains("Inferred"));
+    }
+
+    #[test]
+    fn test_deployment_builder_required_fields() {
+        let deployment = ContractDeployment::builder()
+            .network_id("1")
+            .address("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+            .rpc_url("https://eth.hypersync.xyz")
+            .build()
+            .unwrap();
+        assert_eq!(deployment.network_id, "1");
+        assert_eq!(deployment.rpc_url, "https://eth.hypersync.xyz");
+        assert_eq!(deployment.proxy_address, None);
+        assert_eq!(deployment.start_block, None);
+
+        let err = ContractDeployment::builder()
+            .network_id("1")
+            .rpc_url("https://eth.hypersync.xyz")
+            .build()
+            .unwrap_err();
+        assert!(err.contains("address"));
+    }
+
+    #[test]
+    fn test_deployment_builder_all_fields() {
+        let deployment = ContractDeployment::builder()
+            .network_id("10")
+            .address("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")
+            .rpc_url("https://optimism.hypersync.xyz")
+            .proxy_address("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D")
+            .start_block(1_000)
+            .build()
+            .unwrap();
+        assert_eq!(
+            deployment.address,
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+        );
+        assert_eq!(
+            deployment.proxy_address.as_deref(),
+            Some("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D")
+        );
+        assert_eq!(deployment.start_block, Some(1_000));
+    }
+
+    #[test]
+    fn test_contract_source_predicates() {
+        let abi = ContractSource::Abi {
+            abi: Some("[]".to_string()),
+            url: None,
+            path: None,
+        };
+        assert!(abi.is_abi());
+        assert!(!abi.is_explorer());
+        assert!(!abi.is_inferred());
+        assert_eq!(abi.as_abi(), Some((&Some("[]".to_string()), &None)));
+
+        let explorer = ContractSource::Explorer {
+            api_url: "https://api.etherscan.io/api".to_string(),
+            api_keys: HashMap::new(),
+        };
+        assert!(!explorer.is_abi());
+        assert!(explorer.is_explorer());
+        assert!(!explorer.is_inferred());
+        assert_eq!(explorer.as_abi(), None);
+
+        let inferred = ContractSource::Inferred;
+        assert!(!inferred.is_abi());
+        assert!(!inferred.is_explorer());
+        assert!(inferred.is_inferred());
+        assert_eq!(inferred.as_abi(), None);
+    }
+
+    #[test]
+    fn test_inferred_source_validation() {
+        let contract = create_test_inferred_contract("InferredTest", "1");
+        assert!(IndexerConfig::new("test".to_string(), vec![contract])
+            .validate()
+            .is_ok());
+
+        // Network names resolve the same as ids
+        let contract = create_test_inferred_contract("InferredTest", "Optimism");
+        assert!(IndexerConfig::new("test".to_string(), vec![contract])
+            .validate()
+            .is_ok());
+
+        let contract = create_test_inferred_contract("InferredTest", "999999");
+        assert!(IndexerConfig::new("test".to_string(), vec![contract])
+            .validate()
+            .is_err());
+
+        let mut contract = create_test_inferred_contract("InferredTest", "1");
+        contract.deployments[0].address = "0x1234".to_string();
+        assert!(IndexerConfig::new("test".to_string(), vec![contract])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_duplicate_contract_names_rejected() {
+        let contract1 = create_test_contract("Token", "1");
+        let contract2 = create_test_contract("Token", "10");
+
+        let config = IndexerConfig::new("duplicate_test".to_string(), vec![contract1, contract2]);
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("Token"));
+    }
+
+    #[test]
+    fn test_merge_adds_new_contract() {
+        let mut config = IndexerConfig::new(
+            "merge_test".to_string(),
+            vec![create_test_contract("Contract1", "1")],
+        );
+        let other = IndexerConfig::new(
+            "merge_test".to_string(),
+            vec![create_test_explorer_contract("Contract2", "10")],
+        );
+
+        config.merge(other);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.contracts.len(), 2);
+        assert_eq!(config.contracts[1].name, "Contract2");
+    }
+
+    #[test]
+    fn test_merge_adds_deployment_to_existing_contract() {
+        let mut config = IndexerConfig::new(
+            "merge_test".to_string(),
+            vec![create_test_contract("Greeter", "1")],
+        );
+
+        // Same contract deployed on another network
+        let other = IndexerConfig::new(
+            "merge_test".to_string(),
+            vec![create_test_contract("Greeter", "10")],
+        );
+        config.merge(other);
+        assert_eq!(config.contracts.len(), 1);
+        assert_eq!(config.contracts[0].deployments.len(), 2);
+
+        // Re-merging an identical deployment is a no-op
+        let other = IndexerConfig::new(
+            "merge_test".to_string(),
+            vec![create_test_contract("Greeter", "1")],
+        );
+        config.merge(other);
+        assert_eq!(config.contracts[0].deployments.len(), 2);
+    }
+
+    #[blueprint_sdk::tokio::test]
+    async fn test_resolve_start_block_from_explorer() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let explorer = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api"))
+            .and(query_param("action", "getcontractcreation"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "1",
+                "message": "OK",
+                "result": [{
+                    "contractAddress": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                    "txHash": "0x1234",
+                    "blockNumber": "6082465"
+                }]
+            })))
+            .expect(1)
+            .mount(&explorer)
+            .await;
+
+        let mut deployment = ContractDeployment::builder()
+            .network_id("1")
+            .address("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+            .rpc_url("https://eth.llamarpc.com")
+            .build()
+            .unwrap();
+        let api_url = format!("{}/api", explorer.uri());
+
+        let block = deployment
+            .resolve_start_block(Some(&api_url), None)
+            .await
+            .unwrap();
+        assert_eq!(block, 6082465);
+        assert_eq!(deployment.start_block, Some(6082465));
+
+        // Cached, so the explorer is not queried again
+        let block = deployment
+            .resolve_start_block(Some(&api_url), None)
+            .await
+            .unwrap();
+        assert_eq!(block, 6082465);
+
+        let mut without_explorer = ContractDeployment {
+            start_block: None,
+            ..deployment
+        };
+        assert_eq!(
+            without_explorer
+                .resolve_start_block(None, None)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(without_explorer.start_block, None);
+
+        // A proxied deployment starts at the proxy's creation
+        Mock::given(method("GET"))
+            .and(path("/api"))
+            .and(query_param("action", "getcontractcreation"))
+            .and(query_param(
+                "contractaddresses",
+                "0x43506849D7C04F9138D1A2050bbF3A0c054402dd",
+            ))
+            .and(query_param("apikey", "KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "1",
+                "message": "OK",
+                "result": [{
+                    "contractAddress": "0x43506849d7c04f9138d1a2050bbf3a0c054402dd",
+                    "txHash": "0x5678",
+                    "blockNumber": "16000000"
+                }]
+            })))
+            .with_priority(1)
+            .mount(&explorer)
+            .await;
+        let mut proxied = ContractDeployment {
+            start_block: None,
+            proxy_address: Some("0x43506849D7C04F9138D1A2050bbF3A0c054402dd".to_string()),
+            ..without_explorer
+        };
+        assert_eq!(
+            proxied
+                .resolve_start_block(Some(&api_url), Some("KEY"))
+                .await
+                .unwrap(),
+            16000000
+        );
+    }
+
+    #[blueprint_sdk::tokio::test]
+    async fn test_resolve_start_blocks_of_config() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let explorer = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api"))
+            .and(query_param("apikey", "KEY"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "result": [{ "blockNumber": "100" }]
+            })))
+            .mount(&explorer)
+            .await;
+
+        let explorer_contract = |name: &str, api_url: String| {
+            let mut contract = create_test_explorer_contract(name, "1");
+            contract.source = ContractSource::Explorer {
+                api_url,
+                // Only a lookup with the network's key is answered
+                api_keys: HashMap::from([("1".to_string(), "KEY".to_string())]),
+            };
+            contract
+        };
+        let mut pinned = explorer_contract("Pinned", format!("{}/api", explorer.uri()));
+        pinned.deployments[0].start_block = Some(5);
+        let mut config = IndexerConfig::new(
+            "start_blocks".to_string(),
+            vec![
+                explorer_contract("Found", format!("{}/api", explorer.uri())),
+                pinned,
+                explorer_contract("Unreachable", "http://127.0.0.1:1/api".to_string()),
+                create_test_contract("Local", "1"),
+            ],
+        );
+
+        let warnings = config.resolve_start_blocks().await;
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Unreachable"));
+        let start_blocks: Vec<Option<u64>> = config
+            .contracts
+            .iter()
+            .map(|contract| contract.deployments[0].start_block)
+            .collect();
+        assert_eq!(start_blocks, vec![Some(100), Some(5), None, None]);
+    }
+
+    #[test]
+    fn test_same_network_deployments_are_grouped() {
+        let deployment = |network_id: &str, address: &str, start_block: u64| {
+            ContractDeployment::builder()
+                .network_id(network_id)
+                .address(address)
+                .rpc_url("https://rpc.example.com")
+                .start_block(start_block)
+                .build()
+                .unwrap()
+        };
+        let mut contract = ContractConfig::new(
+            "Pool".to_string(),
+            ContractSource::Inferred,
+            vec![
+                deployment("1", "0x1111111111111111111111111111111111111111", 300),
+                deployment("137", "0x4444444444444444444444444444444444444444", 50),
+                deployment("1", "0x2222222222222222222222222222222222222222", 100),
+                deployment("1", "0x3333333333333333333333333333333333333333", 200),
+            ],
+        );
+
+        let groups = contract.deployments_by_network();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].network_id, "1");
+        assert_eq!(
+            groups[0].addresses,
+            vec![
+                "0x1111111111111111111111111111111111111111",
+                "0x2222222222222222222222222222222222222222",
+                "0x3333333333333333333333333333333333333333",
+            ]
+        );
+        assert_eq!(groups[0].start_block, Some(100));
+        assert_eq!(groups[1].addresses.len(), 1);
+
+        contract.group_deployments();
+        let networks: Vec<_> = contract
+            .deployments
+            .iter()
+            .map(|d| d.network_id.as_str())
+            .collect();
+        assert_eq!(networks, vec!["1", "1", "1", "137"]);
+    }
+
+    #[test]
+    fn test_rpc_conflicts_are_reported() {
+        let deployment = |address: &str, rpc_url: &str| {
+            ContractDeployment::builder()
+                .network_id("1")
+                .address(address)
+                .rpc_url(rpc_url)
+                .build()
+                .unwrap()
+        };
+        let contract = ContractConfig::new(
+            "Token".to_string(),
+            ContractSource::Inferred,
+            vec![
+                deployment(
+                    "0x1111111111111111111111111111111111111111",
+                    "https://eth.llamarpc.com",
+                ),
+                deployment(
+                    "0x2222222222222222222222222222222222222222",
+                    "https://rpc.ankr.com/eth",
+                ),
+            ],
+        );
+        let mut config = IndexerConfig::new("rpc_test".to_string(), vec![contract]);
+
+        // A conflict is a warning, not a validation error
+        assert!(config.validate().is_ok());
+        let warnings = config.validation_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("conflicting RPC URLs"));
+
+        let conflicts = config.normalize_rpc_urls();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].rpc_urls,
+            vec!["https://eth.llamarpc.com", "https://rpc.ankr.com/eth"]
+        );
+        assert!(config.contracts[0]
+            .deployments
+            .iter()
+            .all(|d| d.rpc_url == "https://eth.llamarpc.com"));
+        assert!(config.rpc_conflicts().is_empty());
+    }
+
+    #[test]
+    fn test_parse_single_network_envio_config() {
+        let yaml = r#"
+name: greeter
+networks:
+  - id: 1
+    start_block: 17000000
+    rpc_config:
+      url: https://eth.llamarpc.com
+    contracts:
+      - name: Greeter
+        abi_file_path: abis/Greeter_abi.json
+        handler: src/EventHandlers.ts
+        address:
+          - 0x1111111111111111111111111111111111111111
+          - 0x2222222222222222222222222222222222222222
+        events:
+          - event: NewGreeting(address user, string greeting)
+"#;
+        let config = parse_envio_config(yaml).unwrap();
+        assert_eq!(config.name, "greeter");
+        assert_eq!(config.contracts.len(), 1);
+
+        let contract = &config.contracts[0];
+        assert_eq!(
+            contract.source.get_path(),
+            Some(PathBuf::from("abis/Greeter_abi.json"))
+        );
+        assert_eq!(contract.deployments.len(), 2);
+        for deployment in &contract.deployments {
+            assert_eq!(deployment.network_id, "1");
+            assert_eq!(deployment.rpc_url, "https://eth.llamarpc.com");
+            assert_eq!(deployment.start_block, Some(17000000));
+        }
+        assert_eq!(
+            contract.deployments[1].address,
+            "0x2222222222222222222222222222222222222222"
+        );
+    }
+
+    #[test]
+    fn test_parse_multi_network_envio_config() {
+        let yaml = r#"
+name: usdc
+contracts:
+  - name: USDC
+    handler: src/EventHandlers.ts
+    events:
+      - event: Transfer(address indexed from, address indexed to, uint256 value)
+networks:
+  - id: 1
+    start_block: 0
+    contracts:
+      - name: USDC
+        address: 0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48
+  - id: 10
+    start_block: 0
+    contracts:
+      - name: USDC
+        address:
+          - 0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85
+"#;
+        let config = parse_envio_config(yaml).unwrap();
+        assert_eq!(config.contracts.len(), 1);
+
+        let contract = &config.contracts[0];
+        assert!(contract.source.is_inferred());
+        let networks: Vec<_> = contract
+            .deployments
+            .iter()
+            .map(|d| d.network_id.as_str())
+            .collect();
+        assert_eq!(networks, vec!["1", "10"]);
+        // No rpc_config, envio falls back to HyperSync
+        assert!(contract.deployments.iter().all(|d| d.rpc_url.is_empty()));
+
+        assert!(parse_envio_config("networks: []").is_err());
+    }
+
+    #[test]
+    fn test_block_range_validation() {
+        let deployment = |start_block: u64, end_block: u64| {
+            ContractDeployment::builder()
+                .network_id("1")
+                .address("0x1111111111111111111111111111111111111111")
+                .rpc_url("https://eth.llamarpc.com")
+                .start_block(start_block)
+                .end_block(end_block)
+                .build()
+                .unwrap()
+        };
+
+        let bounded = IndexerConfig::new(
+            "backfill".to_string(),
+            vec![ContractConfig::new(
+                "Campaign".to_string(),
+                ContractSource::Inferred,
+                vec![deployment(18_000_000, 18_100_000)],
+            )],
+        );
+        assert!(bounded.validate().is_ok());
+        assert_eq!(
+            bounded.block_ranges(),
+            vec![("1".to_string(), Some(18_000_000), Some(18_100_000))]
+        );
+
+        let inverted = IndexerConfig::new(
+            "backfill".to_string(),
+            vec![ContractConfig::new(
+                "Campaign".to_string(),
+                ContractSource::Inferred,
+                vec![deployment(18_100_000, 18_000_000)],
+            )],
+        );
+        let err = inverted.validate().unwrap_err();
+        assert!(err.contains("end block 18000000 before start block 18100000"));
+    }
+
+    #[test]
+    fn test_dedup_deployments() {
+        let deployment = |network_id: &str, address: &str, start_block: u64| {
+            ContractDeployment::builder()
+                .network_id(network_id)
+                .address(address)
+                .rpc_url("https://eth.llamarpc.com")
+                .start_block(start_block)
+                .build()
+                .unwrap()
+        };
+
+        let mut contract = ContractConfig::new(
+            "Token".to_string(),
+            ContractSource::Inferred,
+            vec![
+                deployment("1", "0xABCDEFabcdef0000000000000000000000000001", 100),
+                deployment("1", "0xabcdefabcdef0000000000000000000000000001", 100),
+                deployment(
+                    "Ethereum Mainnet",
+                    "0xABCDEFABCDEF0000000000000000000000000001",
+                    100,
+                ),
+                deployment("10", "0xabcdefabcdef0000000000000000000000000001", 100),
+            ],
+        );
+        assert_eq!(contract.dedup_deployments().unwrap(), 2);
+        assert_eq!(contract.deployments.len(), 2);
+
+        // Same address on the same network with a different start block
+        contract.deployments.push(deployment(
+            "10",
+            "0xABCDEFABCDEF0000000000000000000000000001",
+            200,
+        ));
+        let err = contract.dedup_deployments().unwrap_err();
+        assert!(err.contains("conflicting"));
+        assert_eq!(contract.deployments.len(), 3);
+
+        let config = IndexerConfig::new("dedup".to_string(), vec![contract]);
+        assert!(config.validate().unwrap_err().contains("conflicting"));
+
+        // A proxy is the same contract as a plain deployment of the proxy address
+        let proxy = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D";
+        let mut proxied = deployment("1", "0xabcdefabcdef0000000000000000000000000001", 100);
+        proxied.proxy_address = Some(proxy.to_string());
+        let plain = deployment("1", proxy, 100);
+        assert!(proxied.is_same_contract(&plain));
+        assert!(!proxied.is_same_contract(&deployment(
+            "1",
+            "0xabcdefabcdef0000000000000000000000000001",
+            100
+        )));
+        let mut contract = ContractConfig::new(
+            "Token".to_string(),
+            ContractSource::Inferred,
+            vec![proxied.clone(), plain],
+        );
+        assert!(contract.dedup_deployments().unwrap_err().contains(proxy));
+
+        // Two implementations behind the same proxy conflict as well
+        let mut upgraded = deployment("1", "0xabcdefabcdef0000000000000000000000000002", 100);
+        upgraded.proxy_address = Some(proxy.to_lowercase());
+        contract.deployments = vec![proxied.clone(), upgraded];
+        assert!(contract.dedup_deployments().is_err());
+        contract.deployments = vec![proxied.clone(), proxied];
+        assert_eq!(contract.dedup_deployments().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_normalize_addresses() {
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".to_string();
+        let mut config = IndexerConfig::new("checksum".to_string(), vec![contract]);
+
+        config.normalize_addresses().unwrap();
+        assert_eq!(
+            config.contracts[0].deployments[0].address,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+
+        config.contracts[0].deployments[0].proxy_address = Some("0x1234".to_string());
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("Invalid address: 0x1234"));
+        assert!(config.normalize_addresses().is_err());
+    }
+
+    #[test]
+    fn test_fuel_validation() {
+        let config: IndexerConfig =
+            serde_json::from_str(r#"{"name": "evm", "contracts": []}"#).unwrap();
+        assert_eq!(config.ecosystem, Ecosystem::Evm);
+
+        let fuel_address = "0x4a2ce054e3e94155f7092f7365b212f7f45105b74819c623744ebcc5d065c6ac";
+        let mut contract = create_test_contract("Counter", "0");
+        contract.deployments[0].address = fuel_address.to_string();
+        let mut config = IndexerConfig::new("fuel".to_string(), vec![contract]);
+        // Too long for an EVM address
+        assert!(config.validate().is_err());
+
+        config.ecosystem = Ecosystem::Fuel;
+        assert!(config.validate().is_ok());
+        config.normalize_addresses().unwrap();
+        assert_eq!(config.contracts[0].deployments[0].address, fuel_address);
+
+        // Fuel deployments don't need an RPC URL envio could sync from
+        config.contracts[0].deployments[0].rpc_url = "fuel-mainnet".to_string();
+        assert!(config.validate().is_ok());
+
+        config.contracts[0].deployments[0].address =
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string();
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("expected 0x followed by 64 hex digits"));
+
+        config.contracts[0].deployments[0].address = fuel_address.to_string();
+        config.contracts[0].source = ContractSource::Inferred;
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("needs an ABI source"));
+    }
+
+    #[test]
+    fn test_abi_source_validation() {
+        let mut contract = create_test_contract("Token", "1");
+        contract.source = ContractSource::Abi {
+            abi: Some(String::new()),
+            url: None,
+            path: None,
+        };
+        let config = IndexerConfig::new("abi".to_string(), vec![contract.clone()]);
+        assert_eq!(
+            config.validate().unwrap_err(),
+            "Contract Token has an empty inline ABI"
+        );
+
+        contract.source = ContractSource::Abi {
+            abi: Some("[{".to_string()),
+            url: Some("https://example.com/abi.json".to_string()),
+            path: None,
+        };
+        let config = IndexerConfig::new("abi".to_string(), vec![contract.clone()]);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .starts_with("Contract Token has an inline ABI that is not valid JSON"));
+
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: None,
+            path: None,
+        };
+        let config = IndexerConfig::new("abi".to_string(), vec![contract.clone()]);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("Contract Token has an ABI source without"));
+
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: Some("https://example.com/abi.json".to_string()),
+            path: None,
+        };
+        let config = IndexerConfig::new("abi".to_string(), vec![contract.clone()]);
+        assert!(config.validate().is_ok());
+
+        for path in [
+            "../abis/Token.json",
+            "/etc/Token.json",
+            "abis/../../Token.json",
+        ] {
+            contract.source = ContractSource::Abi {
+                abi: None,
+                url: None,
+                path: Some(PathBuf::from(path)),
+            };
+            let config = IndexerConfig::new("abi".to_string(), vec![contract.clone()]);
+            assert!(config
+                .validate()
+                .unwrap_err()
+                .contains("not relative to the ABI directory"));
+        }
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: None,
+            path: Some(PathBuf::from("tokens/Token.json")),
+        };
+        let config = IndexerConfig::new("abi".to_string(), vec![contract]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_contract_and_deployment_lookup() {
+        let mut token = create_test_contract("Token", "1");
+        token.add_deployment(
+            "optimism".to_string(),
+            "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
+            "https://mainnet.optimism.io".to_string(),
+            None,
+            None,
+        );
+        let config = IndexerConfig::new(
+            "lookup".to_string(),
+            vec![token, create_test_contract("Greeter", "137")],
+        );
+
+        let token = config.contract("Token").unwrap();
+        assert_eq!(token.name, "Token");
+        assert!(config.contract("Missing").is_none());
+        assert!(config.contract("token").is_none());
+
+        // Ids and names find the same deployment, however it was configured
+        let mainnet = token.deployment_for_network("1").unwrap();
+        assert_eq!(mainnet.network_id, "1");
+        let optimism = token.deployment_for_network("10").unwrap();
+        assert_eq!(optimism.network_id, "optimism");
+        assert_eq!(token.deployment_for_network("Optimism"), Some(optimism));
+        assert!(token.deployment_for_network("137").is_none());
+        assert!(config
+            .contract("Greeter")
+            .unwrap()
+            .deployment_for_network("polygon")
+            .is_some());
+    }
+
+    #[test]
+    fn test_proxy_deployment_validation() {
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].proxy_address =
+            Some("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string());
+        let config = IndexerConfig::new("proxy".to_string(), vec![contract.clone()]);
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.contracts[0].deployments[0].indexed_address(),
+            "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D"
+        );
+        assert_eq!(
+            config.indexed_addresses(),
+            vec![(
+                "1".to_string(),
+                "Token".to_string(),
+                vec!["0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string()]
+            )]
+        );
+
+        // An inferred ABI would be the proxy's own
+        contract.source = ContractSource::Inferred;
+        let config = IndexerConfig::new("proxy".to_string(), vec![contract.clone()]);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("needs the implementation ABI"));
+
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].proxy_address =
+            Some(contract.deployments[0].address.to_lowercase());
+        let config = IndexerConfig::new("proxy".to_string(), vec![contract]);
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("uses its own address"));
+    }
+
+    #[test]
+    fn test_requires_traces_validation() {
+        let mut contract = create_test_contract("Traced", "1");
+        contract.requires_traces = true;
+        let config = IndexerConfig::new("traced".to_string(), vec![contract]);
+        assert!(config.validate().is_ok());
+
+        let mut contract = create_test_contract("Traced", "42161");
+        contract.requires_traces = true;
+        let config = IndexerConfig::new("traced".to_string(), vec![contract]);
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("requires traces"));
+        assert!(err.contains("Arbitrum"));
+    }
+
+    #[test]
+    fn test_explorer_api_key_per_chain() {
+        let mut contract = create_test_explorer_contract("MultiChain", "1");
+        contract.add_deployment(
+            "Arbitrum".to_string(),
+            "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".to_string(),
+            "https://arb1.arbitrum.io/rpc".to_string(),
+            None,
+            None,
+        );
+        contract.source = ContractSource::Explorer {
+            api_url: "https://api.etherscan.io/api".to_string(),
+            api_keys: HashMap::from([
+                ("1".to_string(), "etherscan_key".to_string()),
+                ("42161".to_string(), "arbiscan_key".to_string()),
+            ]),
+        };
+
+        let keys: Vec<_> = contract
+            .deployments
+            .iter()
+            .map(|deployment| contract.source.explorer_api_key(deployment))
+            .collect();
+        assert_eq!(keys, vec![Some("etherscan_key"), Some("arbiscan_key")]);
+
+        // The network as written wins over its chain id, whatever the map's order
+        contract.source = ContractSource::Explorer {
+            api_url: "https://api.etherscan.io/api".to_string(),
+            api_keys: HashMap::from([
+                ("42161".to_string(), "by_id".to_string()),
+                ("arbitrum".to_string(), "by_name".to_string()),
+            ]),
+        };
+        assert_eq!(
+            contract.source.explorer_api_key(&contract.deployments[1]),
+            Some("by_name")
+        );
+
+        // Networks without a key, and non-explorer sources, have none
+        let base = create_test_explorer_contract("Base", "8453");
+        assert_eq!(base.source.explorer_api_key(&base.deployments[0]), None);
+        let abi = create_test_contract("Abi", "1");
+        assert_eq!(abi.source.explorer_api_key(&abi.deployments[0]), None);
+    }
+
+    #[test]
+    fn test_fallback_rpc_urls() {
+        let deployment = ContractDeployment::builder()
+            .network_id("1")
+            .address("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+            .rpc_url("https://eth.llamarpc.com")
+            .fallback_rpc_url("https://rpc.ankr.com/eth")
+            .fallback_rpc_url("https://cloudflare-eth.com")
+            .build()
+            .unwrap();
+        let mut other = create_test_contract("Other", "1");
+        other.deployments[0].fallback_rpc_urls = vec!["https://rpc.ankr.com/eth".to_string()];
+        let contract = ContractConfig::new(
+            "Token".to_string(),
+            create_test_contract("Token", "1").source,
+            vec![deployment],
+        );
+        let mut config = IndexerConfig::new("fallbacks".to_string(), vec![contract, other]);
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.fallback_rpc_urls(),
+            vec![(
+                "1".to_string(),
+                vec![
+                    "https://rpc.ankr.com/eth".to_string(),
+                    "https://cloudflare-eth.com".to_string(),
+                ]
+            )]
+        );
+
+        config.contracts[1].deployments[0].fallback_rpc_urls = vec!["not a url".to_string()];
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("invalid fallback RPC URL"));
+    }
+
+    #[test]
+    fn test_rpc_url_schemes() {
+        assert_eq!(
+            RpcScheme::of("https://eth.llamarpc.com"),
+            Ok(RpcScheme::Http)
+        );
+        assert_eq!(
+            RpcScheme::of("wss://ethereum-rpc.publicnode.com"),
+            Ok(RpcScheme::WebSocket)
+        );
+        assert!(RpcScheme::of("ftp://eth.example.com").is_err());
+        assert!(RpcScheme::of("http+ws://eth.example.com").is_err());
+        assert!(RpcScheme::of("wss://").is_err());
+
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].rpc_url = "wss://ethereum-rpc.publicnode.com".to_string();
+        let mut config = IndexerConfig::new("ws".to_string(), vec![contract]);
+        assert!(config.validate().is_ok());
+
+        // A network without HyperSync is synced from the primary RPC, which has to be HTTP
+        let mut contract = create_test_contract("Token", "999999");
+        contract.deployments[0].rpc_url = "wss://rpc.example.com".to_string();
+        let unsupported = IndexerConfig::new("ws".to_string(), vec![contract]);
+        let err = unsupported.validate().unwrap_err();
+        assert!(err.contains("needs to be an HTTP URL"));
+
+        config.contracts[0].deployments[0].rpc_url = "ftp://eth.example.com".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("invalid RPC URL"));
+
+        // A WebSocket fallback needs an HTTP fallback to pair with
+        config.contracts[0].deployments[0].rpc_url = "https://eth.llamarpc.com".to_string();
+        config.contracts[0].deployments[0].fallback_rpc_urls =
+            vec!["wss://ethereum-rpc.publicnode.com".to_string()];
+        assert!(config.validate().is_err());
+        config.contracts[0].deployments[0]
+            .fallback_rpc_urls
+            .push("https://rpc.ankr.com/eth".to_string());
+        assert!(config.validate().is_ok());
+    }
 }
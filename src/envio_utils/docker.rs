@@ -0,0 +1,558 @@
+use super::project::{
+    parse_progress_from_log, EnvioError, EnvioProject, IndexerLogMessage, IndexerStatus,
+    DEFAULT_GRAPHQL_PORT,
+};
+use blueprint_sdk::logging::warn;
+use blueprint_sdk::std::collections::HashMap;
+use blueprint_sdk::std::path::Path;
+use blueprint_sdk::tokio;
+use blueprint_sdk::tokio::sync::mpsc;
+use bollard::container::{
+    Config, CreateContainerOptions, InspectContainerOptions, LogOutput, LogsOptions,
+    RemoveContainerOptions, StartContainerOptions, StopContainerOptions,
+};
+use bollard::image::CreateImageOptions;
+use bollard::models::{ContainerInspectResponse, HostConfig, PortBinding};
+use bollard::network::CreateNetworkOptions;
+use bollard::{Docker, API_DEFAULT_VERSION};
+use futures::{StreamExt, TryStreamExt};
+
+/// Timeout in seconds for requests to the Docker daemon
+const DOCKER_TIMEOUT_SECS: u64 = 120;
+
+/// How to reach the Docker daemon
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DockerConnection {
+    /// Use `DOCKER_HOST` or the platform's default socket
+    #[default]
+    LocalDefaults,
+    /// Connect to a specific unix socket (or named pipe on Windows)
+    Socket(String),
+    /// Connect to a daemon over HTTP, e.g. `tcp://10.0.0.5:2375`
+    Http(String),
+}
+
+impl std::fmt::Display for DockerConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockerConnection::LocalDefaults => write!(f, "local defaults"),
+            DockerConnection::Socket(path) => write!(f, "socket {}", path),
+            DockerConnection::Http(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Settings for running indexers inside Docker containers
+#[derive(Debug, Clone)]
+pub struct EnvioDockerConfig {
+    pub connection: DockerConnection,
+    /// Image used to run the envio project
+    pub indexer_image: String,
+    /// Command run inside the indexer container, from the project directory
+    pub indexer_command: Vec<String>,
+    pub postgres_image: String,
+    pub postgres_user: String,
+    pub postgres_password: String,
+    pub postgres_db: String,
+    /// Port the indexer serves GraphQL and health checks on inside its container, each
+    /// indexer publishes it on a host port of its own
+    pub graphql_port: u16,
+}
+
+impl Default for EnvioDockerConfig {
+    fn default() -> Self {
+        Self {
+            connection: DockerConnection::LocalDefaults,
+            indexer_image: "node:20-slim".to_string(),
+            indexer_command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "npm install && npx envio codegen && npx envio start".to_string(),
+            ],
+            postgres_image: "postgres:16".to_string(),
+            postgres_user: "postgres".to_string(),
+            postgres_password: "testing".to_string(),
+            postgres_db: "envio-dev".to_string(),
+            graphql_port: DEFAULT_GRAPHQL_PORT,
+        }
+    }
+}
+
+/// A Docker object created for an indexer
+#[derive(Debug)]
+enum DockerResource {
+    Container(String),
+    Network(String),
+}
+
+/// Runs envio projects in Docker, with a dedicated Postgres container and network per indexer
+pub struct EnvioDocker {
+    docker: Docker,
+    config: EnvioDockerConfig,
+}
+
+impl EnvioDocker {
+    /// Connects to the daemon described by `config.connection`
+    pub fn new(config: EnvioDockerConfig) -> Result<Self, EnvioError> {
+        let docker = match &config.connection {
+            DockerConnection::LocalDefaults => Docker::connect_with_local_defaults(),
+            DockerConnection::Socket(path) => {
+                Docker::connect_with_socket(path, DOCKER_TIMEOUT_SECS, API_DEFAULT_VERSION)
+            }
+            DockerConnection::Http(url) => {
+                Docker::connect_with_http(url, DOCKER_TIMEOUT_SECS, API_DEFAULT_VERSION)
+            }
+        }
+        .map_err(|e| {
+            EnvioError::DockerError(format!(
+                "Failed to connect to Docker at {}: {}",
+                config.connection, e
+            ))
+        })?;
+        Ok(Self { docker, config })
+    }
+
+    pub fn connect_with_socket(
+        path: impl Into<String>,
+        config: EnvioDockerConfig,
+    ) -> Result<Self, EnvioError> {
+        Self::new(EnvioDockerConfig {
+            connection: DockerConnection::Socket(path.into()),
+            ..config
+        })
+    }
+
+    pub fn connect_with_http(
+        url: impl Into<String>,
+        config: EnvioDockerConfig,
+    ) -> Result<Self, EnvioError> {
+        Self::new(EnvioDockerConfig {
+            connection: DockerConnection::Http(url.into()),
+            ..config
+        })
+    }
+
+    /// Checks that the daemon is reachable
+    pub async fn ping(&self) -> Result<(), EnvioError> {
+        self.docker.ping().await.map_err(|e| {
+            EnvioError::DockerError(format!(
+                "Failed to reach Docker at {}: {}",
+                self.config.connection, e
+            ))
+        })?;
+        Ok(())
+    }
+
+    pub fn config(&self) -> &EnvioDockerConfig {
+        &self.config
+    }
+
+    pub fn network_name(id: &str) -> String {
+        format!("{}_network", id)
+    }
+
+    pub fn postgres_container_name(id: &str) -> String {
+        format!("{}_postgres", id)
+    }
+
+    pub fn indexer_container_name(id: &str) -> String {
+        format!("{}_indexer", id)
+    }
+
+    /// Starts the Postgres and indexer containers for a project
+    ///
+    /// Returns the host port the indexer's GraphQL port is published on, which Docker picks so
+    /// that indexers don't collide. Whatever was created is removed again if a step fails.
+    pub async fn start_indexer(&self, project: &EnvioProject) -> Result<u16, EnvioError> {
+        let project_dir = std::fs::canonicalize(&project.dir)?;
+
+        self.pull_image(&self.config.postgres_image).await?;
+        self.pull_image(&self.config.indexer_image).await?;
+
+        let mut created = Vec::new();
+        match self
+            .create_indexer_resources(&project.id, &project_dir, &mut created)
+            .await
+        {
+            Ok(port) => Ok(port),
+            Err(e) => {
+                warn!(
+                    "Failed to start indexer {}, rolling back: {}",
+                    project.id, e
+                );
+                for resource in created.into_iter().rev() {
+                    if let Err(cleanup) = self.remove_resource(&resource).await {
+                        warn!("Failed to remove {:?}: {}", resource, cleanup);
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Creates the network and containers of an indexer, recording each in `created`
+    async fn create_indexer_resources(
+        &self,
+        id: &str,
+        project_dir: &Path,
+        created: &mut Vec<DockerResource>,
+    ) -> Result<u16, EnvioError> {
+        let network = Self::network_name(id);
+        let postgres = Self::postgres_container_name(id);
+        let indexer = Self::indexer_container_name(id);
+
+        self.docker
+            .create_network(CreateNetworkOptions {
+                name: network.clone(),
+                check_duplicate: true,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+        created.push(DockerResource::Network(network.clone()));
+
+        self.docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: postgres.clone(),
+                    platform: None,
+                }),
+                Config {
+                    image: Some(self.config.postgres_image.clone()),
+                    env: Some(vec![
+                        format!("POSTGRES_USER={}", self.config.postgres_user),
+                        format!("POSTGRES_PASSWORD={}", self.config.postgres_password),
+                        format!("POSTGRES_DB={}", self.config.postgres_db),
+                    ]),
+                    host_config: Some(HostConfig {
+                        network_mode: Some(network.clone()),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+        created.push(DockerResource::Container(postgres.clone()));
+        self.docker
+            .start_container(&postgres, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+
+        let container_port = format!("{}/tcp", self.config.graphql_port);
+        // Without a host port Docker allocates a free one
+        let port_bindings = HashMap::from([(
+            container_port.clone(),
+            Some(vec![PortBinding {
+                host_ip: None,
+                host_port: None,
+            }]),
+        )]);
+
+        let mut env = vec![
+            format!("ENVIO_PG_HOST={}", postgres),
+            "ENVIO_PG_PORT=5432".to_string(),
+            format!("ENVIO_PG_USER={}", self.config.postgres_user),
+            format!("ENVIO_POSTGRES_PASSWORD={}", self.config.postgres_password),
+            format!("ENVIO_PG_DATABASE={}", self.config.postgres_db),
+        ];
+        // The container doesn't inherit our environment, so pass the HyperSync token along
+        if let Some(token) = crate::network::hypersync_api_token() {
+            env.push(format!("{}={}", crate::network::HYPERSYNC_TOKEN_ENV, token));
+        }
+
+        self.docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: indexer.clone(),
+                    platform: None,
+                }),
+                Config {
+                    image: Some(self.config.indexer_image.clone()),
+                    cmd: Some(self.config.indexer_command.clone()),
+                    working_dir: Some("/app".to_string()),
+                    env: Some(env),
+                    exposed_ports: Some(HashMap::from([(container_port, HashMap::new())])),
+                    host_config: Some(HostConfig {
+                        binds: Some(vec![format!("{}:/app", project_dir.display())]),
+                        network_mode: Some(network),
+                        port_bindings: Some(port_bindings),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+        created.push(DockerResource::Container(indexer.clone()));
+        self.docker
+            .start_container(&indexer, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+
+        self.host_port(id).await
+    }
+
+    /// Host port the indexer container's GraphQL port was published on
+    pub async fn host_port(&self, id: &str) -> Result<u16, EnvioError> {
+        let inspect = self
+            .docker
+            .inspect_container(
+                &Self::indexer_container_name(id),
+                None::<InspectContainerOptions>,
+            )
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+        Self::published_port(&inspect, self.config.graphql_port).ok_or_else(|| {
+            EnvioError::DockerError(format!(
+                "Port {} of the {} indexer container is not published",
+                self.config.graphql_port, id
+            ))
+        })
+    }
+
+    fn published_port(inspect: &ContainerInspectResponse, container_port: u16) -> Option<u16> {
+        inspect
+            .network_settings
+            .as_ref()?
+            .ports
+            .as_ref()?
+            .get(&format!("{}/tcp", container_port))?
+            .as_ref()?
+            .iter()
+            .find_map(|binding| binding.host_port.as_deref()?.parse().ok())
+    }
+
+    async fn remove_resource(&self, resource: &DockerResource) -> Result<(), EnvioError> {
+        let removed = match resource {
+            DockerResource::Container(name) => {
+                // The container may already be stopped, so only removal errors matter
+                let _ = self
+                    .docker
+                    .stop_container(name, Some(StopContainerOptions { t: 10 }))
+                    .await;
+                self.docker
+                    .remove_container(
+                        name,
+                        Some(RemoveContainerOptions {
+                            force: true,
+                            ..Default::default()
+                        }),
+                    )
+                    .await
+            }
+            DockerResource::Network(name) => self.docker.remove_network(name).await,
+        };
+        removed.map_err(|e| EnvioError::DockerError(format!("{:?}: {}", resource, e)))
+    }
+
+    /// Stops and removes the containers and network of an indexer
+    ///
+    /// Every resource is attempted even if removing an earlier one failed, the errors are
+    /// reported together.
+    pub async fn stop_indexer(&self, id: &str) -> Result<(), EnvioError> {
+        let mut errors = Vec::new();
+        for resource in [
+            DockerResource::Container(Self::indexer_container_name(id)),
+            DockerResource::Container(Self::postgres_container_name(id)),
+            DockerResource::Network(Self::network_name(id)),
+        ] {
+            if let Err(e) = self.remove_resource(&resource).await {
+                errors.push(e.to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(EnvioError::DockerError(errors.join("; ")))
+        }
+    }
+
+    /// Derives the indexer status from its container state and health endpoint
+    pub async fn indexer_status(&self, id: &str) -> Result<IndexerStatus, EnvioError> {
+        let inspect = self
+            .docker
+            .inspect_container(
+                &Self::indexer_container_name(id),
+                None::<InspectContainerOptions>,
+            )
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+
+        let port = Self::published_port(&inspect, self.config.graphql_port);
+        let state = inspect.state.unwrap_or_default();
+        if !state.running.unwrap_or(false) {
+            return match state.exit_code {
+                Some(0) | None => Ok(IndexerStatus::Stopped),
+                Some(code) => Ok(IndexerStatus::Failed(format!(
+                    "Indexer container exited with code {}",
+                    code
+                ))),
+            };
+        }
+
+        let Some(port) = port else {
+            return Ok(IndexerStatus::Starting);
+        };
+        let client = reqwest::Client::new();
+        match client
+            .get(format!("http://localhost:{}/health", port))
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => Ok(IndexerStatus::Running),
+            _ => Ok(IndexerStatus::Starting),
+        }
+    }
+
+    /// Follows the indexer container's log stream
+    ///
+    /// Lines are forwarded as [`IndexerLogMessage`]s, with progress parsed from stdout the
+    /// same way as for a local `envio dev` process.
+    pub fn subscribe_to_logs(&self, id: &str) -> mpsc::Receiver<IndexerLogMessage> {
+        let (tx, rx) = mpsc::channel::<IndexerLogMessage>(100);
+
+        let docker = self.docker.clone();
+        let name = Self::indexer_container_name(id);
+
+        tokio::spawn(async move {
+            let mut logs = Box::pin(docker.logs(
+                &name,
+                Some(LogsOptions::<String> {
+                    follow: true,
+                    stdout: true,
+                    stderr: true,
+                    ..Default::default()
+                }),
+            ));
+
+            while let Some(Ok(output)) = logs.next().await {
+                let (message, is_stderr) = match output {
+                    LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                        (message, false)
+                    }
+                    LogOutput::StdErr { message } => (message, true),
+                    LogOutput::StdIn { .. } => continue,
+                };
+
+                for line in String::from_utf8_lossy(&message).lines() {
+                    let line = line.to_string();
+                    if is_stderr {
+                        let _ = tx.send(IndexerLogMessage::Stderr(line)).await;
+                        continue;
+                    }
+
+                    let progress = parse_progress_from_log(&line);
+                    let _ = tx.send(IndexerLogMessage::Stdout(line)).await;
+                    if let Some(progress) = progress {
+                        let _ = tx.send(IndexerLogMessage::Progress(progress)).await;
+                    }
+                }
+            }
+        });
+
+        rx
+    }
+
+    async fn pull_image(&self, image: &str) -> Result<(), EnvioError> {
+        self.docker
+            .create_image(
+                Some(CreateImageOptions {
+                    from_image: image,
+                    ..Default::default()
+                }),
+                None,
+                None,
+            )
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| EnvioError::DockerError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_socket_connection_is_honored() {
+        let path = "/tmp/envio-nonexistent-docker.sock";
+        let result = match EnvioDocker::connect_with_socket(path, EnvioDockerConfig::default()) {
+            Ok(docker) => {
+                assert_eq!(
+                    docker.config().connection,
+                    DockerConnection::Socket(path.to_string())
+                );
+                docker.ping().await
+            }
+            Err(e) => Err(e),
+        };
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains(path), "unexpected error: {}", err);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running Docker daemon"]
+    async fn test_container_logs_are_streamed() {
+        let docker = EnvioDocker::new(EnvioDockerConfig::default()).unwrap();
+        let id = format!("envio_log_test_{}", uuid::Uuid::new_v4());
+        let name = EnvioDocker::indexer_container_name(&id);
+
+        docker.pull_image("alpine:3").await.unwrap();
+        docker
+            .docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: name.clone(),
+                    platform: None,
+                }),
+                Config {
+                    image: Some("alpine:3".to_string()),
+                    cmd: Some(vec![
+                        "sh".to_string(),
+                        "-c".to_string(),
+                        "echo hello from envio; echo oops >&2".to_string(),
+                    ]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        docker
+            .docker
+            .start_container(&name, None::<StartContainerOptions<String>>)
+            .await
+            .unwrap();
+
+        let mut rx = docker.subscribe_to_logs(&id);
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        while let Ok(Some(msg)) =
+            tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv()).await
+        {
+            match msg {
+                IndexerLogMessage::Stdout(line) => stdout.push(line),
+                IndexerLogMessage::Stderr(line) => stderr.push(line),
+                IndexerLogMessage::Progress(_) => {}
+            }
+        }
+
+        let _ = docker
+            .docker
+            .remove_container(
+                &name,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await;
+
+        assert!(stdout.iter().any(|l| l == "hello from envio"));
+        assert!(stderr.iter().any(|l| l == "oops"));
+    }
+}
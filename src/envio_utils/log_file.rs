@@ -0,0 +1,130 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the log file written into each indexer's project directory
+pub const INDEXER_LOG_FILE: &str = "indexer.log";
+
+/// Size-based rotation settings for persisted indexer logs
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRotationConfig {
+    /// Size at which the current file is rotated
+    pub max_bytes: u64,
+    /// Total number of files kept, including the current one
+    pub max_files: usize,
+}
+
+impl Default for LogRotationConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 10 * 1024 * 1024,
+            max_files: 3,
+        }
+    }
+}
+
+/// A log file that rotates to `<path>.1`, `<path>.2`, ... once it exceeds `max_bytes`
+pub struct RotatingLogFile {
+    path: PathBuf,
+    config: LogRotationConfig,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    pub fn open(path: impl Into<PathBuf>, config: LogRotationConfig) -> std::io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            config,
+            file,
+            size,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the `index`th rotated file, where 1 is the most recent
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 + 1 > self.config.max_bytes {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{}", line)?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+
+        if self.config.max_files <= 1 {
+            self.file = File::create(&self.path)?;
+            self.size = 0;
+            return Ok(());
+        }
+
+        // Shift older files up, dropping the oldest
+        for index in (1..self.config.max_files).rev() {
+            let from = if index == 1 {
+                self.path.clone()
+            } else {
+                self.rotated_path(index - 1)
+            };
+            if from.exists() {
+                std::fs::rename(&from, self.rotated_path(index))?;
+            }
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_log_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = LogRotationConfig {
+            max_bytes: 1024,
+            max_files: 3,
+        };
+        let mut log = RotatingLogFile::open(temp_dir.path().join(INDEXER_LOG_FILE), config)
+            .expect("Failed to open log file");
+
+        let line = "x".repeat(99);
+        for _ in 0..15 {
+            log.write_line(&line).unwrap();
+        }
+
+        assert!(log.path().exists());
+        assert!(log.rotated_path(1).exists());
+        assert!(!log.rotated_path(2).exists());
+        assert!(std::fs::metadata(log.path()).unwrap().len() <= 1024);
+
+        // Further rotations never keep more than `max_files` files
+        for _ in 0..100 {
+            log.write_line(&line).unwrap();
+        }
+        assert!(log.rotated_path(2).exists());
+        assert!(!log.rotated_path(3).exists());
+    }
+}
@@ -1,7 +1,11 @@
 pub mod config;
+pub mod docker;
+pub mod log_file;
 pub mod project;
 
 pub use config::*;
+pub use docker::*;
+pub use log_file::*;
 pub use project::*;
 
 pub const CHAIN_LIST: [&str; 55] = [
@@ -1,13 +1,20 @@
-use super::config::{ContractConfig, ContractSource};
+use super::config::{
+    ContractConfig, ContractSource, Ecosystem, IndexerConfig, NetworkDeployments, RpcScheme,
+};
 use anyhow::Result;
-use blueprint_sdk::std::path::PathBuf;
+use blueprint_sdk::logging::{debug, info, warn};
+use blueprint_sdk::std::path::{Path, PathBuf};
 use blueprint_sdk::tokio;
 use blueprint_sdk::tokio::process::{Child, Command};
-use blueprint_sdk::tokio::sync::mpsc;
-use rexpect::spawn;
+use blueprint_sdk::tokio::sync::{broadcast, mpsc};
+use rexpect::session::spawn_command;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::collections::HashMap;
 use std::io::BufReader;
 use std::sync::Arc;
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Error, Debug)]
 pub enum EnvioError {
@@ -25,10 +32,27 @@ pub enum EnvioError {
     ReqwestError(#[from] reqwest::Error),
     #[error("Serde JSON error: {0}")]
     SerdeJsonError(#[from] serde_json::Error),
+    #[error("Serde YAML error: {0}")]
+    SerdeYamlError(#[from] serde_yaml::Error),
     #[error("Join error: {0}")]
     JoinError(#[from] blueprint_sdk::tokio::task::JoinError),
     #[error("rexpect error: {0}")]
     RexpectError(#[from] rexpect::error::Error),
+    #[error("Unsupported network: {0}")]
+    UnsupportedNetwork(u64),
+    #[error("Failed to fetch ABI from {url}: HTTP {status}")]
+    AbiFetch { url: String, status: u16 },
+    #[error("Failed to get contract ABIs: {}", .0.join("; "))]
+    AbiUnavailable(Vec<String>),
+    #[error("{operation} timed out after {after:?}")]
+    Timeout {
+        operation: &'static str,
+        after: std::time::Duration,
+    },
+    #[error("envio CLI not found, install it with `npm i -g envio`")]
+    EnvioNotFound,
+    #[error("Operation cancelled")]
+    Cancelled,
 }
 
 impl From<EnvioError> for String {
@@ -37,8 +61,98 @@ impl From<EnvioError> for String {
     }
 }
 
+/// Port `envio dev` serves GraphQL and health checks on
+pub const DEFAULT_GRAPHQL_PORT: u16 = 8080;
+
+/// Default limit for the whole interactive `envio init` session
+pub const DEFAULT_INIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Default time a stopped indexer process gets to exit
+pub const DEFAULT_STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Gateway `ipfs://` ABI URLs are fetched through unless another one is configured
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Environment variable the service reads its IPFS gateway from
+pub const IPFS_GATEWAY_ENV: &str = "ENVIO_IPFS_GATEWAY";
+
+/// ABIs fetched at once while initializing a project, low enough to stay clear of rate limits
+const ABI_FETCH_CONCURRENCY: usize = 4;
+
+/// Capacity of the channel returned by [`EnvioManager::subscribe_to_logs`]
+pub const LOG_CHANNEL_CAPACITY: usize = 100;
+
+/// File in a project directory holding the hash of the inputs of its last codegen
+const CODEGEN_STAMP_FILE: &str = ".codegen_hash";
+
+/// File in a project directory holding the hash of the contracts it was initialized with
+const CONFIG_STAMP_FILE: &str = ".config_hash";
+
+/// Time to wait for each line of `envio init` output before treating the prompt as complete
+const PROMPT_READ_TIMEOUT_MS: u64 = 2000;
+
+/// Buffered progress events per [`EnvioManager::stream_init_progress`] subscriber
+const INIT_PROGRESS_CAPACITY: usize = 64;
+
+/// Stage a spawning indexer has reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpawnProgress {
+    /// Resolving the ABI of the named contract
+    FetchingAbi(String),
+    /// Answering the `envio init` prompts
+    RunningInit,
+    /// The project and its `config.yaml` are in place
+    InitComplete,
+    /// Generating indexer code with `envio codegen`
+    RunningCodegen,
+    /// Launching the indexer process
+    Starting,
+}
+
+/// A [`SpawnProgress`] reported for one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitProgressEvent {
+    pub id: String,
+    pub progress: SpawnProgress,
+}
+
+/// Where and how the health of a locally running indexer is checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// Port on localhost serving the health endpoint
+    pub port: u16,
+    /// Path of the health endpoint, e.g. `/health` or Hasura's `/healthz`
+    pub path: String,
+    /// HTTP status a healthy indexer responds with
+    pub expected_status: u16,
+}
+
+impl Default for HealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_GRAPHQL_PORT,
+            path: "/health".to_string(),
+            expected_status: 200,
+        }
+    }
+}
+
+impl HealthCheckConfig {
+    pub fn url(&self) -> String {
+        let separator = if self.path.starts_with('/') { "" } else { "/" };
+        format!("http://localhost:{}{}{}", self.port, separator, self.path)
+    }
+}
+
 pub struct EnvioManager {
     base_dir: PathBuf,
+    init_timeout: std::time::Duration,
+    stop_timeout: std::time::Duration,
+    envio_bin: PathBuf,
+    kill_bin: PathBuf,
+    abi_dir: Option<PathBuf>,
+    ipfs_gateway: String,
+    progress_tx: broadcast::Sender<InitProgressEvent>,
 }
 
 #[derive(Debug)]
@@ -48,9 +162,231 @@ pub struct EnvioProject {
     pub process: Option<Child>,
 }
 
+/// A freshly initialized project
+#[derive(Debug)]
+pub struct InitializedProject {
+    pub project: EnvioProject,
+    /// Contracts left out because their ABI could not be fetched
+    pub skipped_contracts: Vec<String>,
+}
+
+impl EnvioProject {
+    /// OS process id of the indexer, `None` if it was never started or has exited
+    ///
+    /// Takes `&mut self` because telling whether the process exited means reaping it.
+    pub fn pid(&mut self) -> Option<u32> {
+        running_pid(self.process.as_mut()?)
+    }
+}
+
+/// Process id of `child`, or `None` once it has exited
+pub(crate) fn running_pid(child: &mut Child) -> Option<u32> {
+    match child.try_wait() {
+        Ok(None) => child.id(),
+        Ok(Some(_)) | Err(_) => None,
+    }
+}
+
+/// Whether `pid` is alive, a zombie waiting to be reaped counts as exited
+pub(crate) async fn process_alive(pid: u32) -> bool {
+    match Command::new("ps")
+        .args(["-o", "stat=", "-p", &pid.to_string()])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => !String::from_utf8_lossy(&output.stdout)
+            .trim_start()
+            .starts_with('Z'),
+        _ => false,
+    }
+}
+
+/// Whether `pid` runs in `dir`, judged by its working directory or command line
+pub(crate) async fn process_runs_in(pid: u32, dir: &Path) -> bool {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    // Only Linux exposes the working directory, elsewhere the command line has to do
+    if std::fs::read_link(format!("/proc/{}/cwd", pid)).is_ok_and(|cwd| cwd == dir) {
+        return true;
+    }
+    match Command::new("ps")
+        .args(["-o", "args=", "-p", &pid.to_string()])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .any(|arg| Path::new(arg).starts_with(&dir)),
+        _ => false,
+    }
+}
+
 impl EnvioManager {
     pub fn new(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+        let (progress_tx, _) = broadcast::channel(INIT_PROGRESS_CAPACITY);
+        Self {
+            base_dir,
+            init_timeout: DEFAULT_INIT_TIMEOUT,
+            stop_timeout: DEFAULT_STOP_TIMEOUT,
+            envio_bin: PathBuf::from("envio"),
+            kill_bin: PathBuf::from("kill"),
+            abi_dir: None,
+            ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+            progress_tx,
+        }
+    }
+
+    /// Directory holding one project directory per indexer
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
+    /// Returns true if an initialized project with a non-empty `config.yaml` exists for `id`
+    pub fn project_exists(&self, id: &str) -> bool {
+        std::fs::metadata(self.base_dir.join(id).join("config.yaml"))
+            .map(|meta| meta.is_file() && meta.len() > 0)
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the project of `id` exists and was initialized with `contracts`
+    pub fn project_matches(
+        &self,
+        id: &str,
+        contracts: &[ContractConfig],
+        ecosystem: Ecosystem,
+    ) -> bool {
+        let stamp = std::fs::read_to_string(self.base_dir.join(id).join(CONFIG_STAMP_FILE));
+        self.project_exists(id)
+            && init_inputs_hash(contracts, ecosystem)
+                .is_ok_and(|hash| stamp.is_ok_and(|stamp| stamp == hash))
+    }
+
+    /// Checks that the `envio` CLI can be executed
+    pub async fn check_envio_installed(&self) -> Result<(), EnvioError> {
+        match Command::new(&self.envio_bin)
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(EnvioError::EnvioNotFound),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Sets the limit for the interactive `envio init` session
+    pub fn with_init_timeout(mut self, init_timeout: std::time::Duration) -> Self {
+        self.init_timeout = init_timeout;
+        self
+    }
+
+    /// Sets how long a stopped indexer process gets to exit
+    pub fn with_stop_timeout(mut self, stop_timeout: std::time::Duration) -> Self {
+        self.stop_timeout = stop_timeout;
+        self
+    }
+
+    /// How long a stopped indexer process gets to exit
+    pub fn stop_timeout(&self) -> std::time::Duration {
+        self.stop_timeout
+    }
+
+    /// Sets the `envio` executable to run, defaults to `envio` on the `PATH`
+    pub fn with_envio_binary(mut self, envio_bin: impl Into<PathBuf>) -> Self {
+        self.envio_bin = envio_bin.into();
+        self
+    }
+
+    /// Sets the directory ABI file paths are read from, without one they are rejected
+    pub fn with_abi_dir(mut self, abi_dir: impl Into<PathBuf>) -> Self {
+        self.abi_dir = Some(abi_dir.into());
+        self
+    }
+
+    /// Sets the executable processes are signalled with, lets tests stand in a `kill` that fails
+    #[cfg(test)]
+    pub(crate) fn with_kill_binary(mut self, kill_bin: impl Into<PathBuf>) -> Self {
+        self.kill_bin = kill_bin.into();
+        self
+    }
+
+    /// Sends SIGTERM to `pid`, then SIGKILL if it is still alive after the stop timeout
+    pub(crate) async fn terminate_pid(&self, pid: u32) -> Result<(), EnvioError> {
+        Command::new(&self.kill_bin)
+            .arg(pid.to_string())
+            .status()
+            .await?;
+
+        let deadline = std::time::Instant::now() + self.stop_timeout;
+        while process_alive(pid).await {
+            if std::time::Instant::now() >= deadline {
+                warn!("Process {} ignored SIGTERM, killing it", pid);
+                Command::new(&self.kill_bin)
+                    .arg("-9")
+                    .arg(pid.to_string())
+                    .status()
+                    .await?;
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                if process_alive(pid).await {
+                    return Err(EnvioError::ProcessFailed(format!(
+                        "Process {} is still running after SIGKILL",
+                        pid
+                    )));
+                }
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        Ok(())
+    }
+
+    /// Sets the HTTP gateway IPFS-hosted ABIs are fetched through, see [`DEFAULT_IPFS_GATEWAY`]
+    pub fn with_ipfs_gateway(mut self, ipfs_gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = ipfs_gateway.into();
+        self
+    }
+
+    /// Subscribes to the progress of every project this manager initializes and starts
+    ///
+    /// Events are only delivered to receivers subscribed before they are reported.
+    pub fn stream_init_progress(&self) -> broadcast::Receiver<InitProgressEvent> {
+        self.progress_tx.subscribe()
+    }
+
+    /// Reports that project `id` reached `progress`
+    pub fn report_progress(&self, id: &str, progress: SpawnProgress) {
+        // No subscribers is fine, progress is purely informational
+        let _ = self.progress_tx.send(InitProgressEvent {
+            id: id.to_string(),
+            progress,
+        });
+    }
+
+    /// Runs `envio codegen` unless its inputs are unchanged since the last successful run
+    ///
+    /// The inputs are `config.yaml`, `schema.graphql` and the ABI files. `force` always runs
+    /// codegen. Returns whether it ran.
+    pub async fn run_codegen_if_stale(
+        &self,
+        project: &EnvioProject,
+        force: bool,
+    ) -> Result<bool, EnvioError> {
+        let hash = codegen_inputs_hash(&project.dir)?;
+        let stamp_path = project.dir.join(CODEGEN_STAMP_FILE);
+        let up_to_date = project.dir.join("generated").is_dir()
+            && std::fs::read_to_string(&stamp_path).is_ok_and(|stamp| stamp == hash);
+        if up_to_date && !force {
+            debug!(
+                "Generated code of {} is up to date, skipping codegen",
+                project.id
+            );
+            return Ok(false);
+        }
+
+        self.run_codegen(project).await?;
+        std::fs::write(&stamp_path, hash)?;
+        Ok(true)
     }
 
     pub async fn run_codegen(&self, project: &EnvioProject) -> Result<(), EnvioError> {
@@ -62,12 +398,12 @@ impl EnvioManager {
             ));
         }
 
-        // Ensure we're in the project directory
-        std::env::set_current_dir(&project.dir)?;
-
-        let status = Command::new("envio")
+        self.report_progress(&project.id, SpawnProgress::RunningCodegen);
+        // Callers racing codegen against a cancellation drop this future, which kills it
+        let status = Command::new(&self.envio_bin)
             .arg("codegen")
-            .current_dir(&project.dir) // Belt and suspenders approach
+            .current_dir(&project.dir)
+            .kill_on_drop(true)
             .status()
             .await?;
 
@@ -86,7 +422,8 @@ impl EnvioManager {
         }
 
         // Spawn the process with piped output so we can capture logs
-        let child = Command::new("envio")
+        self.report_progress(&project.id, SpawnProgress::Starting);
+        let child = Command::new(&self.envio_bin)
             .arg("dev")
             .current_dir(&project.dir)
             .stdout(std::process::Stdio::piped())
@@ -122,43 +459,56 @@ impl EnvioManager {
     }
 
     pub async fn stop_dev(&self, project: &mut EnvioProject) -> Result<(), EnvioError> {
-        if let Some(mut child) = project.process.take() {
-            println!("Stopping indexer process...");
-
-            // First try to use envio stop command
-            let stop_result = Command::new("envio")
-                .arg("stop")
-                .current_dir(&project.dir)
-                .status()
-                .await;
-
-            // Regardless of stop command result, ensure process is terminated
-            let kill_result = child.kill().await;
-
-            if let Err(e) = kill_result {
-                println!("Warning: Failed to kill process: {}", e);
-
-                // Kill by process ID as a fallback (if we can get it)
-                // The method call returns Option<u32> directly
-                if let Some(id) = child.id() {
-                    println!("Attempting fallback process termination for PID: {}", id);
-                    let _ = Command::new("kill")
-                        .arg("-9")
-                        .arg(id.to_string())
-                        .status()
-                        .await;
-                }
+        // Without a tracked process there is nothing of ours left to clean up
+        let Some(mut child) = project.process.take() else {
+            return Ok(());
+        };
+        info!("Stopping indexer process...");
+
+        // First try to use envio stop command
+        let stop_result = Command::new(&self.envio_bin)
+            .arg("stop")
+            .current_dir(&project.dir)
+            .status()
+            .await;
+
+        // Regardless of stop command result, ensure process is terminated
+        let kill_result = child.kill().await;
+
+        if let Err(e) = kill_result {
+            warn!("Failed to kill process: {}", e);
+
+            // Kill by process ID as a fallback (if we can get it)
+            // The method call returns Option<u32> directly
+            if let Some(id) = child.id() {
+                warn!("Attempting fallback process termination for PID: {}", id);
+                let _ = Command::new(&self.kill_bin)
+                    .arg("-9")
+                    .arg(id.to_string())
+                    .status()
+                    .await;
             }
+        }
 
-            // Wait for the process to completely exit
-            let _ = child.wait().await;
+        // Wait for the process to completely exit, a failed kill must not hang the stop
+        if tokio::time::timeout(self.stop_timeout, child.wait())
+            .await
+            .is_err()
+        {
+            warn!("Indexer process {:?} did not exit", child.id());
+            // Handed back so that stopping can be retried
+            project.process = Some(child);
+            return Err(EnvioError::Timeout {
+                operation: "Waiting for the indexer process to exit",
+                after: self.stop_timeout,
+            });
+        }
 
-            // Log results of stop operation
-            match stop_result {
-                Ok(status) if status.success() => println!("Indexer stopped cleanly"),
-                Ok(status) => println!("Indexer stop command exited with: {:?}", status),
-                Err(e) => println!("Warning: Failed to run stop command: {}", e),
-            }
+        // Log results of stop operation
+        match stop_result {
+            Ok(status) if status.success() => info!("Indexer stopped cleanly"),
+            Ok(status) => warn!("Indexer stop command exited with: {:?}", status),
+            Err(e) => warn!("Failed to run stop command: {}", e),
         }
 
         // Verify no lingering processes
@@ -174,17 +524,17 @@ impl EnvioManager {
 
         let output_str = String::from_utf8_lossy(&output.stdout);
 
-        // Look for processes that match the project directory
-        let project_dir_str = project.dir.to_string_lossy();
-        let dir_str = project_dir_str.to_string(); // Convert to String for contains check
-
         for line in output_str.lines() {
-            if line.contains("envio") && line.contains(&dir_str) {
+            if line.contains("envio") {
                 // Extract PID (first column in ps output)
                 if let Some(pid) = line.split_whitespace().next() {
-                    if let Ok(pid) = pid.parse::<u32>() {
-                        println!("Killing lingering process: {} - {}", pid, line);
-                        let _ = Command::new("kill")
+                    let Ok(pid) = pid.parse::<u32>() else {
+                        continue;
+                    };
+                    // Only processes running in, or pointed at, this exact project directory
+                    if pid != std::process::id() && process_runs_in(pid, &project.dir).await {
+                        warn!("Killing lingering process: {} - {}", pid, line);
+                        let _ = Command::new(&self.kill_bin)
                             .arg("-9")
                             .arg(pid.to_string())
                             .status()
@@ -197,52 +547,118 @@ impl EnvioManager {
         Ok(())
     }
 
-    // New method to monitor indexer progress
+    /// Status of the project's indexer process
+    ///
+    /// An exited process is `Stopped` if it exited successfully, e.g. after reaching its end
+    /// block, and `Failed` otherwise.
     pub async fn monitor_indexer(
         &self,
-        project: &EnvioProject,
+        project: &mut EnvioProject,
+        health_check: &HealthCheckConfig,
     ) -> Result<IndexerStatus, EnvioError> {
-        // For monitoring, we'll need to make a temporary copy since we can't modify the passed reference
-        if let Some(ref process) = project.process {
-            // Since we can't get a mutable reference, we'll use a different approach
-            // Check if the process exists with ps command
-            let output = Command::new("ps")
-                .arg("-p")
-                .arg(process.id().map(|id| id.to_string()).unwrap_or_default())
-                .output()
-                .await?;
-
-            // If exit status is non-zero, process doesn't exist
-            if !output.status.success() {
-                return Ok(IndexerStatus::Stopped);
-            }
-
-            // Process exists, check GraphQL endpoint for health
-            let client = reqwest::Client::new();
-            match client
-                .get("http://localhost:8080/health")
-                .timeout(std::time::Duration::from_secs(5))
-                .send()
-                .await
-            {
-                Ok(response) if response.status().is_success() => {
-                    return Ok(IndexerStatus::Running);
-                }
-                _ => {
-                    // Still starting up
-                    return Ok(IndexerStatus::Starting);
-                }
-            }
+        let Some(process) = project.process.as_mut() else {
+            return Ok(IndexerStatus::Stopped);
+        };
+        match process.try_wait()? {
+            Some(status) if status.success() => Ok(IndexerStatus::Stopped),
+            Some(status) => Ok(IndexerStatus::Failed(format!(
+                "Indexer process exited with {}",
+                status
+            ))),
+            // A process that was already reaped has no id left
+            None => match process.id() {
+                Some(pid) => self.monitor_pid(pid, health_check).await,
+                None => Ok(IndexerStatus::Stopped),
+            },
         }
+    }
 
-        Ok(IndexerStatus::Stopped)
+    /// Status of the indexer running as `pid`, which need not have been spawned by this manager
+    pub async fn monitor_pid(
+        &self,
+        pid: u32,
+        health_check: &HealthCheckConfig,
+    ) -> Result<IndexerStatus, EnvioError> {
+        if !process_alive(pid).await {
+            return Ok(IndexerStatus::Stopped);
+        }
+
+        // Process exists, check its health endpoint
+        let client = reqwest::Client::new();
+        match client
+            .get(health_check.url())
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().as_u16() == health_check.expected_status => {
+                Ok(IndexerStatus::Running)
+            }
+            // Still starting up
+            _ => Ok(IndexerStatus::Starting),
+        }
     }
 
+    /// Initializes a project for EVM `contracts`, failing if any contract's ABI can't be fetched
     pub async fn init_project(
         &self,
         id: &str,
         contracts: Vec<ContractConfig>,
     ) -> Result<EnvioProject, EnvioError> {
+        self.init_project_cancellable(
+            id,
+            contracts,
+            Ecosystem::Evm,
+            false,
+            &CancellationToken::new(),
+        )
+        .await
+        .map(|initialized| initialized.project)
+    }
+
+    /// Like [`Self::init_project`], but aborts when `cancel` fires
+    ///
+    /// A cancelled init kills the `envio init` session. A failed or cancelled init removes the
+    /// project directory it created, so a partial project is never reused. With
+    /// `skip_failed_contracts`, contracts whose ABI can't be fetched are left out instead of
+    /// failing the init.
+    pub async fn init_project_cancellable(
+        &self,
+        id: &str,
+        contracts: Vec<ContractConfig>,
+        ecosystem: Ecosystem,
+        skip_failed_contracts: bool,
+        cancel: &CancellationToken,
+    ) -> Result<InitializedProject, EnvioError> {
+        // A re-init of an existing project must not delete it when it fails
+        let project_dir = self.base_dir.join(id);
+        let created = !project_dir.exists();
+        let result = self
+            .init_project_inner(id, contracts, ecosystem, skip_failed_contracts, cancel)
+            .await;
+        match &result {
+            Err(EnvioError::Cancelled) => {
+                info!("Init of {} cancelled", id);
+            }
+            Err(e) => {
+                warn!("Init of {} failed: {}", id, e);
+            }
+            Ok(_) => return result,
+        }
+        if created {
+            let _ = std::fs::remove_dir_all(&project_dir);
+        }
+        result
+    }
+
+    async fn init_project_inner(
+        &self,
+        id: &str,
+        contracts: Vec<ContractConfig>,
+        ecosystem: Ecosystem,
+        skip_failed_contracts: bool,
+        cancel: &CancellationToken,
+    ) -> Result<InitializedProject, EnvioError> {
         let project_dir = self.base_dir.join(id);
         std::fs::create_dir_all(&project_dir)?;
 
@@ -252,56 +668,166 @@ impl EnvioManager {
             ));
         }
 
+        self.check_envio_installed().await?;
+
         // Get ABIs and set up directory
         let abis_dir = project_dir.join("abis");
         std::fs::create_dir_all(&abis_dir)?;
 
         // Get ABI for each contract and write to file
-        for contract in contracts.iter() {
-            match self.get_abi(contract).await {
-                Ok(abi) => {
-                    let abi_path = abis_dir.join(format!("{}_abi.json", contract.name));
-                    println!("Writing {:?}, ABI to file: {:?}", contract.name, abi_path);
-                    std::fs::write(&abi_path, abi)?;
-                }
-                Err(_) => {
-                    continue;
-                }
-            }
+        let abi_failures = tokio::select! {
+            failures = self.fetch_abis(id, &contracts, &project_dir) => failures?,
+            _ = cancel.cancelled() => return Err(EnvioError::Cancelled),
+        };
+
+        if !abi_failures.is_empty() && !skip_failed_contracts {
+            return Err(EnvioError::AbiUnavailable(
+                abi_failures
+                    .into_iter()
+                    .map(|(name, e)| format!("{}: {}", name, e))
+                    .collect(),
+            ));
+        }
+        let skipped_contracts: Vec<String> =
+            abi_failures.into_iter().map(|(name, _)| name).collect();
+        let contracts: Vec<ContractConfig> = contracts
+            .into_iter()
+            .filter(|contract| !skipped_contracts.contains(&contract.name))
+            .collect();
+        if contracts.is_empty() {
+            return Err(EnvioError::InvalidState(
+                "No contract ABI could be fetched".into(),
+            ));
         }
+        for name in &skipped_contracts {
+            warn!(
+                "Skipping contract {} of {}, its ABI could not be fetched",
+                name, id
+            );
+        }
+        // Only the contracts the project has, so a config with skipped ones is initialized again
+        let config_hash = init_inputs_hash(&contracts, ecosystem)?;
 
         let is_first_contract_inferred = contracts[0].source.is_inferred();
 
-        // Clone the values needed for the blocking task
-        let project_dir_clone = project_dir.clone();
+        self.report_progress(id, SpawnProgress::RunningInit);
+        // The process-wide working directory is shared by every indexer, so only the child's is set
+        let mut init = std::process::Command::new(&self.envio_bin);
+        init.arg("init").current_dir(&project_dir);
+        if !is_first_contract_inferred {
+            init.args(["contract-import", "local"]);
+        }
+        let mut session = spawn_command(init, Some(PROMPT_READ_TIMEOUT_MS))?;
+
+        Self::drive_init_session(
+            &mut session,
+            &contracts,
+            ecosystem,
+            self.init_timeout,
+            cancel,
+        )
+        .await?;
+
+        debug!("Waiting for envio process to exit...");
+        let status = session.process.wait()?;
+        match status {
+            rexpect::process::wait::WaitStatus::Signaled(pid, signal, code) => {
+                debug!(
+                    "Envio process (PID: {}) exited with signal {} code {}",
+                    pid, signal, code
+                );
+            }
+            rexpect::process::wait::WaitStatus::Exited(pid, code) => {
+                debug!("Envio process (PID: {}) exited with code {}", pid, code);
+            }
+            status => {
+                warn!("Envio process exited with unexpected status: {:?}", status);
+                return Err(EnvioError::ProcessFailed(
+                    "Envio process exited unexpectedly".to_string(),
+                ));
+            }
+        }
+        let config_path = project_dir.join("config.yaml");
+        if !config_path.exists() {
+            return Err(EnvioError::InvalidState(
+                "Project initialization failed: config.yaml not created".into(),
+            ));
+        }
 
-        std::env::set_current_dir(&project_dir_clone)?;
+        // `envio init` never asks for block ranges or RPCs, so write them into the generated config
+        apply_network_settings(
+            &config_path,
+            &IndexerConfig {
+                ecosystem,
+                ..IndexerConfig::new(id.to_string(), contracts.clone())
+            },
+        )?;
 
-        let mut session = if is_first_contract_inferred {
-            spawn("envio init", Some(2000))?
-        } else {
-            spawn("envio init contract-import local", Some(2000))?
-        };
-        // session.send_line("envio init contract-import local")?;
+        // Lets a later spawn tell whether this project can be reused for its config
+        std::fs::write(project_dir.join(CONFIG_STAMP_FILE), config_hash)?;
 
-        let mut current_contract_idx = 0;
-        let mut current_deployment_idx = 0;
+        self.report_progress(id, SpawnProgress::InitComplete);
+        Ok(InitializedProject {
+            project: EnvioProject {
+                id: id.to_string(),
+                dir: project_dir,
+                process: None,
+            },
+            skipped_contracts,
+        })
+    }
+
+    /// Answers `envio init` prompts until the project is ready.
+    ///
+    /// The session is killed and an error returned if it takes longer than `timeout` or
+    /// `cancel` fires.
+    async fn drive_init_session(
+        session: &mut rexpect::session::PtySession,
+        contracts: &[ContractConfig],
+        ecosystem: Ecosystem,
+        timeout: std::time::Duration,
+        cancel: &CancellationToken,
+    ) -> Result<(), EnvioError> {
+        let deadline = std::time::Instant::now() + timeout;
+
+        // Same-network deployments are added as extra addresses of one envio contract
+        let networks: Vec<Vec<NetworkDeployments>> = contracts
+            .iter()
+            .map(|contract| contract.deployments_by_network())
+            .collect();
+
+        let mut cursor = InitCursor::default();
 
         let mut success = false;
 
         loop {
+            if cancel.is_cancelled() {
+                let _ = session.process.exit();
+                return Err(EnvioError::Cancelled);
+            }
+            if std::time::Instant::now() >= deadline {
+                let _ = session.process.exit();
+                return Err(EnvioError::Timeout {
+                    operation: "init",
+                    after: timeout,
+                });
+            }
+
             match Self::handle_envio_prompts(
-                &mut session,
-                &contracts,
-                &mut current_contract_idx,
-                &mut current_deployment_idx,
+                session,
+                contracts,
+                &networks,
+                ecosystem,
+                &mut cursor,
                 &mut success,
+                deadline,
+                cancel,
             )
             .await
             {
                 Ok(true) => {
                     // If we're finished, kill the process directly instead of trying to exit cleanly
-                    println!("Project template ready");
+                    debug!("Project template ready");
                     session.send_control('c')?;
                     session.send_line("exit")?;
                     session.send_line("quit")?;
@@ -326,64 +852,22 @@ impl EnvioManager {
             }
         }
 
-        println!("Waiting for envio process to exit...");
-        let status = session.process.wait()?;
-        match status {
-            rexpect::process::wait::WaitStatus::Signaled(pid, signal, code) => {
-                println!(
-                    "Envio process (PID: {}) exited with signal {} code {}",
-                    pid, signal, code
-                );
-            }
-            rexpect::process::wait::WaitStatus::Exited(pid, code) => {
-                println!("Envio process (PID: {}) exited with code {}", pid, code);
-            }
-            status => {
-                println!("Envio process exited with unexpected status: {:?}", status);
-                return Err(EnvioError::ProcessFailed(
-                    "Envio process exited unexpectedly".to_string(),
-                ));
-            }
-        }
-        println!("Envio process completed, verifying project setup...");
-        let current_dir = std::env::current_dir()?;
-        println!("Current dir: {:?}", current_dir);
-
-        // Since we're already in the project directory, just use current_dir
-        let project_dir = current_dir;
-
-        println!("Project dir: {:?}", project_dir);
-        println!("Project dir exists: {:?}", project_dir.exists());
-
-        // Use current directory for config file check
-        let config_path = project_dir.join("config.yaml");
-        println!("Config path: {:?}", config_path);
-        println!("Config path exists: {:?}", config_path.exists());
-        println!("Config path is file: {:?}", config_path.is_file());
-
-        if !config_path.exists() {
-            return Err(EnvioError::InvalidState(
-                "Project initialization failed: config.yaml not created".into(),
-            ));
-        }
-
-        println!("Project setup verified, returning `EnvioProject`");
-        Ok(EnvioProject {
-            id: id.to_string(),
-            dir: project_dir,
-            process: None,
-        })
+        Ok(())
     }
 
     async fn handle_envio_prompts(
         session: &mut rexpect::session::PtySession,
         contracts: &[ContractConfig],
-        current_contract_idx: &mut usize,
-        current_deployment_idx: &mut usize,
+        networks: &[Vec<NetworkDeployments>],
+        ecosystem: Ecosystem,
+        cursor: &mut InitCursor,
         success: &mut bool,
+        deadline: std::time::Instant,
+        cancel: &CancellationToken,
     ) -> Result<bool, EnvioError> {
         let mut prompt = String::new();
-        loop {
+        // Stop reading at the deadline or on cancellation even if output never pauses
+        while std::time::Instant::now() < deadline && !cancel.is_cancelled() {
             match session.read_line() {
                 Ok(line) => prompt.push_str(&format!("{}\n", line)),
                 Err(rexpect::error::Error::EOF { .. }) => break,
@@ -409,19 +893,19 @@ impl EnvioManager {
             .collect();
 
         if !current_prompt.is_empty() {
-            println!("Current prompt: {}", current_prompt);
+            debug!("Current prompt: {}", current_prompt);
         }
 
         if !options.is_empty() {
-            println!("Available options:");
+            debug!("Available options:");
             for option in options {
-                println!("  {}", option);
+                debug!("  {}", option);
             }
         }
 
         match current_prompt {
             s if s.contains("Specify a folder name") => {
-                println!("Handling folder name prompt");
+                debug!("Handling folder name prompt");
                 session.send(".")?;
                 session.flush()?;
                 session.send_control('m')?;
@@ -431,31 +915,40 @@ impl EnvioManager {
                 || s.contains("Typescript")
                 || s.contains("ReScript") =>
             {
-                println!("Handling language selection");
+                debug!("Handling language selection");
+                session.send_control('m')?;
+            }
+            s if s.contains("Choose an initialization option") => {
+                debug!("Handling initialization option prompt");
+                // Template is the default, Contract Import is next
+                session.send("\x1B[B")?; // Down arrow
                 session.send_control('m')?;
             }
             s if s.contains("Choose blockchain ecosystem") => {
-                println!("Handling blockchain ecosystem selection");
-                // EVM and Fuel are options but for now we only support EVM
+                debug!("Handling blockchain ecosystem selection: {:?}", ecosystem);
+                // EVM is listed first, Fuel second
+                if ecosystem == Ecosystem::Fuel {
+                    session.send("\x1B[B")?; // Down arrow
+                }
                 session.send_control('m')?;
             }
             s if s.contains("Which events would you like to index?")
                 || (s.contains("space to select one") && s.contains("type to filter")) =>
             {
-                println!("Handling events prompt");
+                debug!("Handling events prompt");
                 session.send_control('m')?;
             }
             s if s.contains("What is the path to your json abi file?") => {
-                let contract = &contracts[*current_contract_idx];
-                let abi_path = format!("./abis/{}_abi.json", contract.name);
+                let contract = &contracts[cursor.contract];
+                let abi_path = format!("./{}", abi_file_path(&contract.name).display());
 
                 session.send(&abi_path)?;
                 session.flush()?;
                 session.send_control('m')?;
             }
             s if s.contains("Would you like to import from a block explorer or a local abi") => {
-                println!("Handling block explorer vs local ABI prompt");
-                let contract = &contracts[*current_contract_idx];
+                debug!("Handling block explorer vs local ABI prompt");
+                let contract = &contracts[cursor.contract];
 
                 if contract.source.is_explorer() || contract.source.is_inferred() {
                     // For block explorer, just hit enter
@@ -467,13 +960,13 @@ impl EnvioManager {
                 }
             }
             s if s.contains("Which blockchain would you like to import a contract from?") => {
-                println!("Handling blockchain selection");
-                let contract = &contracts[*current_contract_idx];
-                let network_id: u64 = (&contract.deployments[*current_deployment_idx].network_id).parse().unwrap_or_default();
+                debug!("Handling blockchain selection");
+                let network = &networks[cursor.contract][cursor.network];
+                let network_id: u64 = network.network_id.parse().unwrap_or_default();
                 // Get the network info from definitions
                 let network_info = crate::network::definitions::SUPPORTED_NETWORKS
                     .get(&network_id)
-                    .expect("Network ID not found in supported networks");
+                    .ok_or(EnvioError::UnsupportedNetwork(network_id))?;
 
                 // Convert network name to lowercase and convert spaces to hyphens
                 let network_name = network_info.name.to_lowercase().replace(' ', "-");
@@ -482,7 +975,7 @@ impl EnvioManager {
                 let chain_idx = crate::envio_utils::CHAIN_LIST
                     .iter()
                     .position(|&x| x == network_name)
-                    .expect("Network not found in chain list");
+                    .ok_or(EnvioError::UnsupportedNetwork(network_id))?;
 
                 // Send down arrow key chain_idx times
                 for _ in 0..chain_idx {
@@ -491,61 +984,69 @@ impl EnvioManager {
                 session.send_control('m')?;
             }
             s if s.contains("Choose network:") || s.contains("<Enter Network Id>") => {
-                println!("Handling network selection");
+                debug!("Handling network selection");
+                if ecosystem == Ecosystem::Fuel {
+                    // Fuel only offers its own networks, there is no id to enter
+                    let network_id = &networks[cursor.contract][cursor.network].network_id;
+                    let position = crate::network::fuel_network_position(network_id).ok_or(
+                        EnvioError::UnsupportedNetwork(network_id.parse().unwrap_or_default()),
+                    )?;
+                    for _ in 0..position {
+                        session.send("\x1B[B")?; // Down arrow
+                    }
+                }
+                // EVM picks `<Enter Network Id>`, the id is entered at the next prompt
                 session.send_control('m')?;
             }
             s if s.contains("Enter the network id:") => {
-                println!("Handling network id prompt");
-                let contract = &contracts[*current_contract_idx];
-                let deployment = &contract.deployments[*current_deployment_idx];
-                session.send(&deployment.network_id.to_string())?;
+                debug!("Handling network id prompt");
+                let network = &networks[cursor.contract][cursor.network];
+                session.send(&network.network_id)?;
                 session.flush()?;
                 session.send_control('m')?;
             }
-            s if s.contains("What is the name of this contract?")
-                || s.contains("Use the proxy address if your abi is a proxy implementation") =>
-            {
-                println!("Handling contract name prompt");
-                let contract = &contracts[*current_contract_idx];
+            s if s.contains("What is the name of this contract?") => {
+                debug!("Handling contract name prompt");
+                let contract = &contracts[cursor.contract];
                 session.send(&contract.name)?;
                 session.flush()?;
                 session.send_control('m')?;
             }
-            s if s.contains("What is the address of the contract?") => {
-                println!("Handling contract address prompt");
-                let contract = &contracts[*current_contract_idx];
-                let deployment = &contract.deployments[*current_deployment_idx];
-                let address = if !deployment.address.starts_with("0x") {
-                    format!("0x{}", deployment.address)
+            s if s.contains("What is the address of the contract?")
+                || s.contains("Use the proxy address if your abi is a proxy implementation") =>
+            {
+                debug!("Handling contract address prompt");
+                // The ABI is the implementation's, but the proxy is what emits the events
+                let network = &networks[cursor.contract][cursor.network];
+                let address = &network.addresses[cursor.address];
+                let address = if !address.starts_with("0x") {
+                    format!("0x{}", address)
                 } else {
-                    deployment.address.clone()
+                    address.to_string()
                 };
                 session.send(&address)?;
                 session.flush()?;
                 session.send_control('m')?;
             }
             s if s.contains("Would you like to add another contract?") => {
-                println!("Handling add another contract prompt");
-                let contract = &contracts[*current_contract_idx];
-                let deployment = &contract.deployments[*current_deployment_idx];
-
-                // Check if there are more deployments for this contract
-                if *current_deployment_idx + 1 < contract.deployments.len() {
-                    let next_deployment = &contract.deployments[*current_deployment_idx + 1];
-                    *current_deployment_idx += 1;
-
-                    if next_deployment.network_id == deployment.network_id {
-                        // Same network, different address
-                        session.send("\x1B[B")?; // Down arrow once
-                    } else {
-                        // Different network
-                        session.send("\x1B[B")?; // Down arrow
-                        session.send("\x1B[B")?; // Down arrow again
-                    }
-                } else if *current_contract_idx + 1 < contracts.len() {
+                debug!("Handling add another contract prompt");
+                let contract_networks = &networks[cursor.contract];
+
+                if cursor.address + 1 < contract_networks[cursor.network].addresses.len() {
+                    // Same network, different address
+                    cursor.address += 1;
+                    session.send("\x1B[B")?; // Down arrow once
+                } else if cursor.network + 1 < contract_networks.len() {
+                    // Different network
+                    cursor.network += 1;
+                    cursor.address = 0;
+                    session.send("\x1B[B")?; // Down arrow
+                    session.send("\x1B[B")?; // Down arrow again
+                } else if cursor.contract + 1 < contracts.len() {
                     // Move to next contract
-                    *current_contract_idx += 1;
-                    *current_deployment_idx = 0;
+                    cursor.contract += 1;
+                    cursor.network = 0;
+                    cursor.address = 0;
                     session.send("\x1B[B")?; // Down arrow
                     session.send("\x1B[B")?; // Down arrow
                     session.send("\x1B[B")?; // Down arrow
@@ -553,29 +1054,44 @@ impl EnvioManager {
                 session.flush()?;
                 session.send_control('m')?;
             }
+            s if s.contains("Add your API token:") && crate::network::hypersync_api_token().is_some() => {
+                debug!("Handling HyperSync API token entry");
+                let token = crate::network::hypersync_api_token().unwrap_or_default();
+                session.send(&token)?;
+                session.flush()?;
+                session.send_control('m')?;
+            }
+            s if s.contains("Add an API token for HyperSync to your .env file?")
+                && crate::network::hypersync_api_token().is_some() =>
+            {
+                debug!("Handling HyperSync API token prompt, adding existing token");
+                session.send("\x1B[B")?;
+                session.flush()?;
+                session.send_control('m')?;
+            }
             s if s.contains("Add an API token for HyperSync to your .env file?")
                 | s.contains("Add your API token:") =>
             {
-                println!("Handling HyperSync API token prompt");
+                debug!("Handling HyperSync API token prompt");
                 session.send("\x1B[B")?;
                 session.send("\x1B[B")?;
                 session.flush()?;
                 session.send_control('m')?;
             }
             s if s.contains("Project template ready") => {
-                println!("Handling project template ready prompt");
+                debug!("Handling project template ready prompt");
                 session.send_control('m')?;
 				        return Ok(true)
             }
             s if s.contains("You can always visit 'https://envio.dev/app/api-tokens' and add a token later to your .env file.") => {
-              println!("Handling final prompt");
+              debug!("Handling final prompt");
               *success = true;
               session.send_control('m')?;
               return Ok(true)
             }
             _ => {
                 if !current_prompt.is_empty() {
-                    println!("Unhandled prompt: {}", current_prompt);
+                    warn!("Unhandled prompt: {}", current_prompt);
                     session.send_control('m')?;
                 }
             }
@@ -583,16 +1099,76 @@ impl EnvioManager {
 
         Ok(false)
     }
-    async fn get_abi(&self, contract: &ContractConfig) -> Result<String, EnvioError> {
+    /// Fetches the ABIs of `contracts` into the project directory, a few at a time
+    ///
+    /// Returns the contracts whose ABI could not be fetched along with the error.
+    async fn fetch_abis(
+        &self,
+        id: &str,
+        contracts: &[ContractConfig],
+        project_dir: &Path,
+    ) -> Result<Vec<(String, String)>, EnvioError> {
+        use futures::StreamExt;
+
+        // envio fetches the ABI itself for inferred contracts
+        let mut fetches = futures::stream::iter(
+            contracts
+                .iter()
+                .filter(|contract| !contract.source.is_inferred()),
+        )
+        .map(|contract| async move {
+            self.report_progress(id, SpawnProgress::FetchingAbi(contract.name.clone()));
+            (contract, self.get_abi(contract).await)
+        })
+        .buffer_unordered(ABI_FETCH_CONCURRENCY);
+
+        let mut failures = Vec::new();
+        while let Some((contract, result)) = fetches.next().await {
+            match result {
+                Ok(abi) => {
+                    let abi_path = project_dir.join(abi_file_path(&contract.name));
+                    std::fs::write(&abi_path, abi)?;
+                }
+                Err(e) => failures.push((contract.name.clone(), e.to_string())),
+            }
+        }
+        // Report failures in config order, whichever finished first
+        failures.sort_by_key(|(name, _)| contracts.iter().position(|c| c.name == *name));
+        Ok(failures)
+    }
+
+    pub(crate) async fn get_abi(&self, contract: &ContractConfig) -> Result<String, EnvioError> {
         match &contract.source {
-            ContractSource::Abi { abi, url } => match (abi, url) {
-                (Some(abi_str), _) => Ok(abi_str.to_string()),
-                (_, Some(url)) => fetch_abi_from_url(url).await,
+            ContractSource::Abi { abi, url, path } => match (abi, path, url) {
+                (Some(abi_str), _, _) => Ok(abi_str.to_string()),
+                (_, Some(path), _) => match &self.abi_dir {
+                    Some(abi_dir) => read_abi_file(abi_dir, path),
+                    None => Err(EnvioError::InvalidState(format!(
+                        "ABI file {} given but no ABI directory is configured",
+                        path.display()
+                    ))),
+                },
+                (_, _, Some(url)) => match ipfs_gateway_url(url, &self.ipfs_gateway) {
+                    Some(gateway_url) => {
+                        let abi = fetch_abi_from_url(&gateway_url).await?;
+                        // Gateways serve whatever the CID points to
+                        let is_abi = serde_json::from_str::<serde_json::Value>(&abi)
+                            .is_ok_and(|abi| abi.is_array());
+                        if !is_abi {
+                            return Err(EnvioError::InvalidState(format!(
+                                "Content of {} is not an ABI JSON array",
+                                url
+                            )));
+                        }
+                        Ok(abi)
+                    }
+                    None => fetch_abi_from_url(url).await,
+                },
                 _ => Err(EnvioError::InvalidState(
                     "No ABI source provided".to_string(),
                 )),
             },
-            ContractSource::Explorer { api_url } => {
+            ContractSource::Explorer { api_url, .. } => {
                 let api_url = if api_url.is_empty() {
                     std::env::var("ENVIO_API_URL")
                         .unwrap_or_else(|_| "https://envio.dev/api".to_string())
@@ -600,7 +1176,41 @@ impl EnvioManager {
                     api_url.to_string()
                 };
 
-                fetch_abi_from_url(&api_url).await
+                // The ABI is the same on every chain, so the key of each deployment's network is
+                // tried in turn until one fetch succeeds
+                let mut api_keys: Vec<Option<&str>> = Vec::new();
+                for deployment in &contract.deployments {
+                    let api_key = contract.source.explorer_api_key(deployment);
+                    if !api_keys.contains(&api_key) {
+                        api_keys.push(api_key);
+                    }
+                }
+                if api_keys.is_empty() {
+                    api_keys.push(None);
+                }
+
+                let mut result = Err(EnvioError::InvalidState(
+                    "No explorer API key tried".to_string(),
+                ));
+                for api_key in api_keys {
+                    result = match api_key {
+                        Some(api_key) => {
+                            let mut url = reqwest::Url::parse(&api_url).map_err(|e| {
+                                EnvioError::InvalidState(format!(
+                                    "Invalid explorer API URL {}: {}",
+                                    api_url, e
+                                ))
+                            })?;
+                            url.query_pairs_mut().append_pair("apikey", api_key);
+                            fetch_abi_from_url(url.as_str()).await
+                        }
+                        None => fetch_abi_from_url(&api_url).await,
+                    };
+                    if result.is_ok() {
+                        break;
+                    }
+                }
+                result
             }
             ContractSource::Inferred => Err(EnvioError::InvalidState(
                 "No ABI source provided, it is inferred from the contract address and network"
@@ -611,20 +1221,29 @@ impl EnvioManager {
 
     /// Subscribe to log messages from a running indexer process
     /// Returns a receiver channel that will receive log messages
+    ///
+    /// This takes the process's stdout and stderr, so it can only succeed once per process.
+    /// `ServiceContext` does so when starting an indexer and fans the stream out from there.
     pub fn subscribe_to_logs(
         &self,
         project: &mut EnvioProject,
     ) -> Result<mpsc::Receiver<IndexerLogMessage>, EnvioError> {
         // Create a channel for sending log messages
-        let (tx, rx) = mpsc::channel::<IndexerLogMessage>(100);
+        let (tx, rx) = mpsc::channel::<IndexerLogMessage>(LOG_CHANNEL_CAPACITY);
 
         if let Some(child) = &mut project.process {
             // Take ownership of stdout and stderr
             let stdout = child.stdout.take();
             let stderr = child.stderr.take();
+            if stdout.is_none() && stderr.is_none() {
+                return Err(EnvioError::InvalidState(
+                    "Process output is already subscribed to".into(),
+                ));
+            }
 
+            // The readers never wait on the consumer, a full pipe would stall the indexer
             if let Some(stdout) = stdout {
-                let tx_clone = tx.clone();
+                let mut sender = LogSender::new(tx.clone(), IndexerLogMessage::Stdout);
 
                 // Use tokio's async io
                 tokio::spawn(async move {
@@ -633,18 +1252,19 @@ impl EnvioManager {
                     let mut lines = reader.lines();
 
                     while let Ok(Some(line)) = lines.next_line().await {
-                        let _ = tx_clone.send(IndexerLogMessage::Stdout(line.clone())).await;
-
                         // Try to parse progress information
-                        if let Some(progress) = parse_progress_from_log(&line) {
-                            let _ = tx_clone.send(IndexerLogMessage::Progress(progress)).await;
+                        let progress = parse_progress_from_log(&line);
+                        sender.send(IndexerLogMessage::Stdout(line));
+                        if let Some(progress) = progress {
+                            sender.send(IndexerLogMessage::Progress(progress));
                         }
                     }
+                    sender.finish().await;
                 });
             }
 
             if let Some(stderr) = stderr {
-                let tx_clone = tx.clone();
+                let mut sender = LogSender::new(tx.clone(), IndexerLogMessage::Stderr);
 
                 // Use tokio's async io
                 tokio::spawn(async move {
@@ -653,8 +1273,9 @@ impl EnvioManager {
                     let mut lines = reader.lines();
 
                     while let Ok(Some(line)) = lines.next_line().await {
-                        let _ = tx_clone.send(IndexerLogMessage::Stderr(line)).await;
+                        sender.send(IndexerLogMessage::Stderr(line));
                     }
+                    sender.finish().await;
                 });
             }
         } else {
@@ -665,10 +1286,477 @@ impl EnvioManager {
     }
 }
 
+/// Position of `envio init` in the contracts being imported
+///
+/// Indexes the contract, its network group and the address within that group.
+#[derive(Default)]
+struct InitCursor {
+    contract: usize,
+    network: usize,
+    address: usize,
+}
+
+/// Forwards the messages of one output stream without ever waiting on the consumer
+///
+/// Messages that don't fit into the channel are dropped and counted. A `[N lines dropped]`
+/// marker on the same stream reports them once there is room again.
+struct LogSender {
+    tx: mpsc::Sender<IndexerLogMessage>,
+    marker: fn(String) -> IndexerLogMessage,
+    dropped: usize,
+}
+
+impl LogSender {
+    fn new(tx: mpsc::Sender<IndexerLogMessage>, marker: fn(String) -> IndexerLogMessage) -> Self {
+        Self {
+            tx,
+            marker,
+            dropped: 0,
+        }
+    }
+
+    fn send(&mut self, msg: IndexerLogMessage) {
+        use mpsc::error::TrySendError;
+
+        if self.dropped > 0 {
+            let marker = (self.marker)(format!("[{} lines dropped]", self.dropped));
+            match self.tx.try_send(marker) {
+                Ok(()) => self.dropped = 0,
+                Err(TrySendError::Full(_)) => {
+                    self.dropped += 1;
+                    return;
+                }
+                // Nobody is listening, the output is still drained
+                Err(TrySendError::Closed(_)) => return,
+            }
+        }
+        if let Err(TrySendError::Full(_)) = self.tx.try_send(msg) {
+            self.dropped += 1;
+        }
+    }
+
+    /// Reports lines dropped at the end of the stream, which no longer needs to keep reading
+    async fn finish(self) {
+        if self.dropped > 0 {
+            let marker = (self.marker)(format!("[{} lines dropped]", self.dropped));
+            let _ = self.tx.send(marker).await;
+        }
+    }
+}
+
+/// Sets `start_block`, `end_block`, RPCs and the watched contract addresses of each network in
+/// an envio `config.yaml`
+///
+/// Contracts behind a proxy are watched at the proxy address and keep the implementation's ABI.
+fn apply_network_settings(config_path: &Path, config: &IndexerConfig) -> Result<(), EnvioError> {
+    let mut envio_config: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(config_path)?)?;
+    let Some(networks) = envio_config
+        .get_mut("networks")
+        .and_then(|n| n.as_sequence_mut())
+    else {
+        return Ok(());
+    };
+
+    let ranges = config.block_ranges();
+    // Fuel networks are synced from HyperFuel, which `envio init` already configured
+    let is_evm = config.ecosystem == Ecosystem::Evm;
+    let (primaries, fallbacks) = if is_evm {
+        (config.rpc_urls(), config.fallback_rpc_urls())
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let addresses = config.indexed_addresses();
+    for network in networks {
+        let id = match network.get("id") {
+            Some(serde_yaml::Value::Number(id)) => id.to_string(),
+            Some(serde_yaml::Value::String(id)) => id.clone(),
+            _ => continue,
+        };
+        if let Some((_, start_block, end_block)) = ranges.iter().find(|(n, _, _)| *n == id) {
+            if let Some(start_block) = start_block {
+                network["start_block"] = (*start_block).into();
+            }
+            if let Some(end_block) = end_block {
+                network["end_block"] = (*end_block).into();
+            }
+        }
+        let mut rpcs: Vec<EnvioRpcEntry> = Vec::new();
+        // The token stays out of the file, envio reads it from its environment
+        let hypersync = id
+            .parse::<u64>()
+            .ok()
+            .filter(|_| is_evm)
+            .and_then(|id| crate::network::hypersync_url(id).ok());
+        if let Some(url) = &hypersync {
+            network["hypersync_config"] =
+                serde_yaml::to_value(HashMap::from([("url", url.as_str())]))?;
+        } else if let Some((_, url)) = primaries
+            .iter()
+            .find(|(n, url)| *n == id && RpcScheme::of(url) == Ok(RpcScheme::Http))
+        {
+            // Networks without HyperSync have to be synced from the deployment's RPC, which
+            // envio only syncs from over HTTP
+            rpcs.push(EnvioRpcEntry {
+                url,
+                ws: None,
+                purpose: "sync",
+            });
+        }
+        if let Some((_, urls)) = fallbacks.iter().find(|(n, _)| *n == id) {
+            // The fallbacks only take over when the primary source is unavailable.
+            // envio takes WebSocket endpoints as the `ws` of an HTTP entry, paired in order.
+            let (http, ws): (Vec<&String>, Vec<&String>) = urls
+                .iter()
+                .partition(|url| !matches!(RpcScheme::of(url), Ok(RpcScheme::WebSocket)));
+            rpcs.extend(http.iter().enumerate().map(|(i, url)| EnvioRpcEntry {
+                url,
+                ws: ws.get(i).map(|ws| ws.as_str()),
+                purpose: "fallback",
+            }));
+        }
+        if !rpcs.is_empty() {
+            network["rpc"] = serde_yaml::to_value(rpcs)?;
+        }
+        if let Some(contracts) = network
+            .get_mut("contracts")
+            .and_then(|c| c.as_sequence_mut())
+        {
+            for contract in contracts {
+                let Some(name) = contract.get("name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                if let Some((_, _, watched)) = addresses
+                    .iter()
+                    .find(|(n, contract_name, _)| *n == id && contract_name == name)
+                {
+                    contract["address"] = serde_yaml::to_value(watched)?;
+                }
+            }
+        }
+    }
+
+    std::fs::write(config_path, serde_yaml::to_string(&envio_config)?)?;
+    Ok(())
+}
+
+/// Writes the `config.yaml`, `schema.graphql` and ABI files `envio init` would create for
+/// `config` into `dir`, and returns the config and schema
+///
+/// `abis` maps contract names to their ABI. Contracts without one are listed without events,
+/// like envio does for ABIs it infers itself.
+pub fn generate_project_files(
+    dir: &Path,
+    config: &IndexerConfig,
+    abis: &HashMap<String, String>,
+) -> Result<(String, String), EnvioError> {
+    std::fs::create_dir_all(dir.join("abis"))?;
+
+    let mut contracts = Vec::new();
+    let mut schema = String::new();
+    let mut networks: Vec<(String, serde_yaml::Value)> = Vec::new();
+    for contract in &config.contracts {
+        let mut entry = serde_yaml::Mapping::new();
+        entry.insert("name".into(), contract.name.clone().into());
+        if let Some(abi) = abis.get(&contract.name) {
+            let abi_path = abi_file_path(&contract.name);
+            std::fs::write(dir.join(&abi_path), abi)?;
+
+            let events = abi_events(abi)?;
+            for event in &events {
+                schema.push_str(&event.schema_type(&contract.name));
+            }
+            let events: Vec<serde_yaml::Value> = events
+                .iter()
+                .map(|event| {
+                    let mut entry = serde_yaml::Mapping::new();
+                    entry.insert("event".into(), event.signature().into());
+                    entry.into()
+                })
+                .collect();
+            entry.insert(
+                "abi_file_path".into(),
+                format!("./{}", abi_path.display()).into(),
+            );
+            entry.insert("handler".into(), "./src/EventHandlers.ts".into());
+            entry.insert("events".into(), events.into());
+        }
+        contracts.push(serde_yaml::Value::from(entry));
+
+        // `apply_network_settings` fills in the addresses, block ranges and RPCs
+        for deployment in &contract.deployments {
+            let id = deployment.resolve_network_to_number();
+            let index = match networks.iter().position(|(n, _)| *n == id) {
+                Some(index) => index,
+                None => {
+                    let mut network = serde_yaml::Mapping::new();
+                    let id_value: serde_yaml::Value = match id.parse::<u64>() {
+                        Ok(number) => number.into(),
+                        Err(_) => id.clone().into(),
+                    };
+                    network.insert("id".into(), id_value);
+                    network.insert("start_block".into(), 0u64.into());
+                    network.insert("contracts".into(), serde_yaml::Sequence::new().into());
+                    networks.push((id, network.into()));
+                    networks.len() - 1
+                }
+            };
+            let network_contracts = networks[index].1["contracts"]
+                .as_sequence_mut()
+                .expect("contracts are always a sequence");
+            let listed = network_contracts
+                .iter()
+                .any(|c| c["name"].as_str() == Some(contract.name.as_str()));
+            if !listed {
+                let mut entry = serde_yaml::Mapping::new();
+                entry.insert("name".into(), contract.name.clone().into());
+                network_contracts.push(entry.into());
+            }
+        }
+    }
+
+    let mut envio_config = serde_yaml::Mapping::new();
+    envio_config.insert("name".into(), config.name.clone().into());
+    envio_config.insert("contracts".into(), contracts.into());
+    envio_config.insert(
+        "networks".into(),
+        networks
+            .into_iter()
+            .map(|(_, network)| network)
+            .collect::<Vec<_>>()
+            .into(),
+    );
+
+    let config_path = dir.join("config.yaml");
+    std::fs::write(&config_path, serde_yaml::to_string(&envio_config)?)?;
+    apply_network_settings(&config_path, config)?;
+    std::fs::write(dir.join("schema.graphql"), &schema)?;
+
+    Ok((std::fs::read_to_string(&config_path)?, schema))
+}
+
+/// An event of an ABI, with its parameters as `(name, type, indexed)`
+struct AbiEvent {
+    name: String,
+    params: Vec<(String, String, bool)>,
+}
+
+impl AbiEvent {
+    /// Human readable signature, the form envio lists events in
+    fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(name, ty, indexed)| {
+                let indexed = if *indexed { " indexed" } else { "" };
+                format!("{}{} {}", ty, indexed, name).trim_end().to_string()
+            })
+            .collect();
+        format!("{}({})", self.name, params.join(", "))
+    }
+
+    /// GraphQL entity envio generates for the event of `contract`
+    fn schema_type(&self, contract: &str) -> String {
+        let mut fields = String::from("  id: ID!\n");
+        for (i, (name, ty, _)) in self.params.iter().enumerate() {
+            let name = if name.is_empty() {
+                format!("_{}", i)
+            } else {
+                name.clone()
+            };
+            fields.push_str(&format!("  {}: {}\n", name, graphql_type(ty)));
+        }
+        format!("type {}_{} {{\n{}}}\n\n", contract, self.name, fields)
+    }
+}
+
+fn abi_events(abi: &str) -> Result<Vec<AbiEvent>, EnvioError> {
+    let abi: serde_json::Value = serde_json::from_str(abi)?;
+    let items = abi.as_array().ok_or_else(|| {
+        EnvioError::InvalidState("ABI is not a JSON array of entries".to_string())
+    })?;
+
+    Ok(items
+        .iter()
+        .filter(|item| item["type"] == "event")
+        .filter_map(|item| {
+            let params = item["inputs"]
+                .as_array()
+                .map(|inputs| {
+                    inputs
+                        .iter()
+                        .map(|input| {
+                            (
+                                input["name"].as_str().unwrap_or_default().to_string(),
+                                abi_param_type(input),
+                                input["indexed"].as_bool().unwrap_or(false),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(AbiEvent {
+                name: item["name"].as_str()?.to_string(),
+                params,
+            })
+        })
+        .collect())
+}
+
+/// Solidity type of an ABI parameter, with tuples spelled out from their components
+fn abi_param_type(param: &serde_json::Value) -> String {
+    let ty = param["type"].as_str().unwrap_or_default();
+    match ty.strip_prefix("tuple") {
+        Some(suffix) => {
+            let components: Vec<String> = param["components"]
+                .as_array()
+                .map(|components| components.iter().map(abi_param_type).collect())
+                .unwrap_or_default();
+            format!("({}){}", components.join(","), suffix)
+        }
+        None => ty.to_string(),
+    }
+}
+
+/// GraphQL type envio maps a Solidity type to
+fn graphql_type(ty: &str) -> String {
+    if let Some(inner) = ty.strip_suffix(']') {
+        let inner = &inner[..inner.rfind('[').unwrap_or(0)];
+        return format!("[{}]!", graphql_type(inner));
+    }
+    let scalar = if ty.starts_with("uint") || ty.starts_with("int") {
+        "BigInt"
+    } else if ty == "bool" {
+        "Boolean"
+    } else {
+        // Addresses, strings, bytes and tuples
+        "String"
+    };
+    format!("{}!", scalar)
+}
+
+/// Hash of the contracts and ecosystem a project is initialized with
+fn init_inputs_hash(
+    contracts: &[ContractConfig],
+    ecosystem: Ecosystem,
+) -> Result<String, EnvioError> {
+    let mut hasher = Keccak256::new();
+    hasher.update(serde_json::to_vec(&(contracts, ecosystem))?);
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Hash of the files `envio codegen` generates code from, missing files are skipped
+fn codegen_inputs_hash(project_dir: &Path) -> Result<String, EnvioError> {
+    let mut inputs = vec![
+        project_dir.join("config.yaml"),
+        project_dir.join("schema.graphql"),
+    ];
+    if let Ok(entries) = std::fs::read_dir(project_dir.join("abis")) {
+        let mut abis: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        abis.sort();
+        inputs.extend(abis);
+    }
+
+    let mut hasher = Keccak256::new();
+    for input in inputs {
+        if !input.is_file() {
+            continue;
+        }
+        // The name keeps moving content between files from hashing the same
+        hasher.update(input.to_string_lossy().as_bytes());
+        hasher.update(std::fs::read(&input)?);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Path of a contract's ABI file, relative to its project directory
+///
+/// The name hash keeps apart contracts whose names only differ in case or in characters that are
+/// replaced to make the name safe for a file name.
+pub fn abi_file_path(contract_name: &str) -> PathBuf {
+    let hash = Keccak256::digest(contract_name.as_bytes());
+    let hash: String = hash[..4].iter().map(|b| format!("{:02x}", b)).collect();
+    let name: String = contract_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    PathBuf::from("abis").join(format!("{}_{}_abi.json", name, hash))
+}
+
+/// An entry of a network's `rpc` list in an envio `config.yaml`
+#[derive(Serialize)]
+struct EnvioRpcEntry<'a> {
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ws: Option<&'a str>,
+    #[serde(rename = "for")]
+    purpose: &'static str,
+}
+
+/// Reads the ABI at `path` within `abi_dir`, refusing anything that resolves outside of it
+fn read_abi_file(abi_dir: &Path, path: &Path) -> Result<String, EnvioError> {
+    if !super::config::is_contained_path(path) {
+        return Err(EnvioError::InvalidState(format!(
+            "ABI file {} is not relative to the ABI directory",
+            path.display()
+        )));
+    }
+    let full_path = abi_dir.join(path);
+    if !full_path.is_file() {
+        return Err(EnvioError::InvalidState(format!(
+            "ABI file {} does not exist",
+            path.display()
+        )));
+    }
+    // Symlinks may still point elsewhere, so compare the resolved locations
+    let full_path = full_path.canonicalize()?;
+    if !full_path.starts_with(abi_dir.canonicalize()?) {
+        return Err(EnvioError::InvalidState(format!(
+            "ABI file {} resolves outside of the ABI directory",
+            path.display()
+        )));
+    }
+    let abi = std::fs::read_to_string(full_path)?;
+    // Fail early rather than letting `envio init` choke on a malformed file
+    serde_json::from_str::<serde_json::Value>(&abi)?;
+    Ok(abi)
+}
+
+/// HTTP URL of an `ipfs://<cid>` or `ipfs/<cid>` URL on `gateway`, `None` for other URLs
+pub fn ipfs_gateway_url(url: &str, gateway: &str) -> Option<String> {
+    let path = url
+        .strip_prefix("ipfs://")
+        .or_else(|| url.strip_prefix("/ipfs/"))
+        .or_else(|| url.strip_prefix("ipfs/"))?;
+    // Also accept the `ipfs://ipfs/<cid>` form some tools produce
+    let path = path.strip_prefix("ipfs/").unwrap_or(path);
+    if path.is_empty() {
+        return None;
+    }
+    Some(format!("{}/{}", gateway.trim_end_matches('/'), path))
+}
+
 async fn fetch_abi_from_url(url: &str) -> Result<String, EnvioError> {
-    reqwest::get(url)
+    let response = reqwest::get(url)
         .await
-        .map_err(|e| EnvioError::ProcessFailed(format!("Failed to fetch ABI: {}", e)))?
+        .map_err(|e| EnvioError::ProcessFailed(format!("Failed to fetch ABI: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(EnvioError::AbiFetch {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+    response
         .text()
         .await
         .map_err(|e| EnvioError::ProcessFailed(format!("Failed to read ABI response: {}", e)))
@@ -685,7 +1773,11 @@ pub enum IndexerLogMessage {
     Progress(IndexerProgress),
 }
 
-#[derive(Debug, Clone)]
+/// Lifecycle state of an indexer
+///
+/// Serializes tagged, e.g. `{"status":"Running"}` or `{"status":"Failed","reason":"..."}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason")]
 pub enum IndexerStatus {
     Configured,
     Starting,
@@ -716,8 +1808,20 @@ impl From<IndexerStatus> for String {
     }
 }
 
+/// GraphQL endpoint envio announces on startup, e.g. `GraphQL: http://localhost:8080/v1/graphql`
+pub(crate) fn parse_graphql_endpoint(line: &str) -> Option<String> {
+    let announced = &line[line.find("GraphQL:")? + "GraphQL:".len()..];
+    // The URL may be followed by color codes
+    let endpoint = announced
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == '\x1b')
+        .next()?;
+    (endpoint.starts_with("http://") || endpoint.starts_with("https://"))
+        .then(|| endpoint.to_string())
+}
+
 /// Parse progress information from a log line
-fn parse_progress_from_log(line: &str) -> Option<IndexerProgress> {
+pub(crate) fn parse_progress_from_log(line: &str) -> Option<IndexerProgress> {
     let mut progress = IndexerProgress::default();
 
     // Parse events processed
@@ -777,6 +1881,16 @@ fn parse_progress_from_log(line: &str) -> Option<IndexerProgress> {
         }
     }
 
+    // Without an explicit percentage, derive it from the block range
+    if progress.percentage.is_none() {
+        if let (Some(current), Some(total)) = (progress.blocks_current, progress.blocks_total) {
+            if total > 0 {
+                // The head may move past a stale total
+                progress.percentage = Some((current.min(total) * 100) / total);
+            }
+        }
+    }
+
     // Parse ETA
     if let Some(eta_idx) = line.find("Sync Time ETA:") {
         let eta_part = &line[eta_idx + "Sync Time ETA:".len()..];
@@ -797,3 +1911,894 @@ fn parse_progress_from_log(line: &str) -> Option<IndexerProgress> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_deployment, create_test_contract, write_stub_envio};
+    use blueprint_sdk::tokio;
+    use rexpect::spawn;
+
+    #[tokio::test]
+    async fn test_init_session_selects_ecosystem() {
+        let contracts = vec![create_test_contract("Greeter", "1")];
+
+        for (ecosystem, expected) in [(Ecosystem::Evm, ""), (Ecosystem::Fuel, "\x1B[B")] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let answer = temp_dir.path().join("answer");
+            let script = temp_dir.path().join("init.sh");
+            // Records the keys sent at the ecosystem prompt, then finishes like `envio init`
+            std::fs::write(
+                &script,
+                format!(
+                    "echo '? Choose blockchain ecosystem'\nread -r answer\nprintf '%s' \"$answer\" > {}\necho '? Project template ready'\nsleep 5\n",
+                    answer.display()
+                ),
+            )
+            .unwrap();
+            let mut session = spawn(&format!("sh {}", script.display()), Some(500)).unwrap();
+
+            EnvioManager::drive_init_session(
+                &mut session,
+                &contracts,
+                ecosystem,
+                std::time::Duration::from_secs(10),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                std::fs::read_to_string(&answer).unwrap(),
+                expected,
+                "Unexpected selection for {:?}",
+                ecosystem
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_init_session_selects_network() {
+        // Records the keys sent at the network prompt, then finishes like `envio init`
+        fn network_prompt(dir: &Path, answer: &Path) -> PathBuf {
+            let script = dir.join("init.sh");
+            std::fs::write(
+                &script,
+                format!(
+                    "echo '? Choose network:'\nread -r answer\nprintf '%s' \"$answer\" > {}\necho '? Project template ready'\nsleep 5\n",
+                    answer.display()
+                ),
+            )
+            .unwrap();
+            script
+        }
+
+        for (ecosystem, network_id, expected) in [
+            (Ecosystem::Evm, "1", ""),
+            (Ecosystem::Fuel, "9889", ""),
+            (Ecosystem::Fuel, "0", "\x1B[B"),
+            (Ecosystem::Fuel, "testnet", "\x1B[B"),
+        ] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let answer = temp_dir.path().join("answer");
+            let script = network_prompt(temp_dir.path(), &answer);
+            let mut session = spawn(&format!("sh {}", script.display()), Some(500)).unwrap();
+
+            EnvioManager::drive_init_session(
+                &mut session,
+                &[create_test_contract("Greeter", network_id)],
+                ecosystem,
+                std::time::Duration::from_secs(10),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(
+                std::fs::read_to_string(&answer).unwrap(),
+                expected,
+                "Unexpected selection of {} for {:?}",
+                network_id,
+                ecosystem
+            );
+        }
+
+        // An id Fuel doesn't know can't be picked from the menu
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let script = network_prompt(temp_dir.path(), &temp_dir.path().join("answer"));
+        let mut session = spawn(&format!("sh {}", script.display()), Some(500)).unwrap();
+        let result = EnvioManager::drive_init_session(
+            &mut session,
+            &[create_test_contract("Greeter", "1")],
+            Ecosystem::Fuel,
+            std::time::Duration::from_secs(10),
+            &CancellationToken::new(),
+        )
+        .await;
+        assert!(matches!(result, Err(EnvioError::UnsupportedNetwork(1))));
+    }
+
+    #[tokio::test]
+    async fn test_init_session_prompts_each_network_once() {
+        let mut contract = create_test_contract("Greeter", "1");
+        contract.deployments = vec![
+            create_deployment(
+                "1",
+                Some("0x1111111111111111111111111111111111111111".into()),
+                None,
+                None,
+                None,
+            ),
+            create_deployment(
+                "137",
+                Some("0x3333333333333333333333333333333333333333".into()),
+                None,
+                None,
+                None,
+            ),
+            create_deployment(
+                "1",
+                Some("0x2222222222222222222222222222222222222222".into()),
+                None,
+                None,
+                None,
+            ),
+        ];
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let answers = temp_dir.path().join("answers");
+        let script = temp_dir.path().join("init.sh");
+        // Records the address entered and the choice made for each deployment
+        let step = format!(
+            "echo '? What is the address of the contract?'\nread -r answer\nprintf '%s\\n' \"$answer\" >> {answers}\necho '? Would you like to add another contract?'\nread -r answer\nprintf '%s\\n' \"$answer\" >> {answers}\n",
+            answers = answers.display()
+        );
+        std::fs::write(
+            &script,
+            format!(
+                "{}echo '? Project template ready'\nsleep 5\n",
+                step.repeat(3)
+            ),
+        )
+        .unwrap();
+        let mut session = spawn(&format!("sh {}", script.display()), Some(500)).unwrap();
+
+        EnvioManager::drive_init_session(
+            &mut session,
+            &[contract],
+            Ecosystem::Evm,
+            std::time::Duration::from_secs(10),
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+
+        // Both mainnet addresses go under one network before moving on to polygon
+        assert_eq!(
+            std::fs::read_to_string(&answers).unwrap(),
+            "0x1111111111111111111111111111111111111111\n\x1B[B\n\
+             0x2222222222222222222222222222222222222222\n\x1B[B\x1B[B\n\
+             0x3333333333333333333333333333333333333333\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_init_session_times_out() {
+        let contracts = vec![create_test_contract("Greeter", "1")];
+
+        // A session that keeps printing but never reaches a known prompt
+        let mut session = spawn(
+            "sh -c 'while true; do echo working; sleep 0.1; done'",
+            Some(200),
+        )
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let result = EnvioManager::drive_init_session(
+            &mut session,
+            &contracts,
+            Ecosystem::Evm,
+            std::time::Duration::from_secs(1),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        match result {
+            Err(EnvioError::Timeout { operation, .. }) => assert_eq!(operation, "init"),
+            other => panic!("Expected init timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_init_session_cancelled() {
+        let contracts = vec![create_test_contract("Greeter", "1")];
+        let mut session = spawn(
+            "sh -c 'while true; do echo working; sleep 0.1; done'",
+            Some(200),
+        )
+        .unwrap();
+
+        // The session blocks the current thread, so cancel from another one
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            canceller.cancel();
+        });
+
+        let result = EnvioManager::drive_init_session(
+            &mut session,
+            &contracts,
+            Ecosystem::Evm,
+            std::time::Duration::from_secs(30),
+            &cancel,
+        )
+        .await;
+
+        assert!(matches!(result, Err(EnvioError::Cancelled)));
+        assert!(!matches!(
+            session.process.status(),
+            Some(rexpect::process::wait::WaitStatus::StillAlive)
+        ));
+    }
+
+    #[test]
+    fn test_apply_block_ranges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config_path,
+            "name: backfill\nnetworks:\n- id: 1\n  start_block: 0\n  contracts: []\n- id: 10\n  start_block: 0\n  contracts: []\n",
+        )
+        .unwrap();
+
+        let mut contract = create_test_contract("Campaign", "1");
+        contract.deployments[0].start_block = Some(100);
+        contract.deployments[0].end_block = Some(200);
+        let config = IndexerConfig::new("backfill".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(written["networks"][0]["start_block"].as_u64(), Some(100));
+        assert_eq!(written["networks"][0]["end_block"].as_u64(), Some(200));
+        // Networks without deployments are left alone
+        assert_eq!(written["networks"][1]["start_block"].as_u64(), Some(0));
+        assert!(written["networks"][1].get("end_block").is_none());
+    }
+
+    #[test]
+    fn test_apply_fuel_network_settings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config_path,
+            "name: fuel\necosystem: fuel\nnetworks:\n- id: 0\n  start_block: 0\n  contracts: []\n",
+        )
+        .unwrap();
+
+        let mut contract = create_test_contract("Counter", "0");
+        contract.deployments[0].address =
+            "0x4a2ce054e3e94155f7092f7365b212f7f45105b74819c623744ebcc5d065c6ac".to_string();
+        contract.deployments[0].start_block = Some(100);
+        contract.deployments[0].fallback_rpc_urls = vec!["https://backup.example.com".to_string()];
+        let mut config = IndexerConfig::new("fuel".to_string(), vec![contract]);
+        config.ecosystem = Ecosystem::Fuel;
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(written["networks"][0]["start_block"].as_u64(), Some(100));
+        // Fuel keeps the HyperFuel source `envio init` configured
+        assert!(written["networks"][0].get("hypersync_config").is_none());
+        assert!(written["networks"][0].get("rpc").is_none());
+    }
+
+    #[test]
+    fn test_parse_graphql_endpoint() {
+        assert_eq!(
+            parse_graphql_endpoint("GraphQL: http://localhost:8081/v1/graphql"),
+            Some("http://localhost:8081/v1/graphql".to_string())
+        );
+        assert_eq!(
+            parse_graphql_endpoint("  GraphQL:   http://localhost:8080/v1/graphql\x1b[0m"),
+            Some("http://localhost:8080/v1/graphql".to_string())
+        );
+        assert_eq!(parse_graphql_endpoint("GraphQL: starting"), None);
+        assert_eq!(parse_graphql_endpoint("Events Processed: 10"), None);
+    }
+
+    #[test]
+    fn test_progress_percentage_from_blocks() {
+        let progress =
+            parse_progress_from_log("Events Processed: 1,200 blocks: 2,500/10,000 synced").unwrap();
+        assert_eq!(progress.blocks_current, Some(2500));
+        assert_eq!(progress.blocks_total, Some(10000));
+        assert_eq!(progress.percentage, Some(25));
+
+        let progress = parse_progress_from_log("blocks: 120/100 synced").unwrap();
+        assert_eq!(progress.percentage, Some(100));
+
+        let progress = parse_progress_from_log("blocks: 0/0 synced").unwrap();
+        assert_eq!(progress.blocks_total, Some(0));
+        assert_eq!(progress.percentage, None);
+    }
+
+    #[tokio::test]
+    async fn test_abi_from_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let abi_dir = temp_dir.path().join("abis");
+        std::fs::create_dir_all(&abi_dir).unwrap();
+        std::fs::write(abi_dir.join("Greeter.json"), crate::test_utils::GREETER_ABI).unwrap();
+        std::fs::write(temp_dir.path().join("Outside.json"), "[]").unwrap();
+
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf()).with_abi_dir(&abi_dir);
+        let mut contract = create_test_contract("Greeter", "1");
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: Some("http://127.0.0.1:1/unused.json".to_string()),
+            path: Some(PathBuf::from("Greeter.json")),
+        };
+        let abi = manager.get_abi(&contract).await.unwrap();
+        assert_eq!(abi, crate::test_utils::GREETER_ABI);
+
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: None,
+            path: Some(PathBuf::from("Missing.json")),
+        };
+        match manager.get_abi(&contract).await {
+            Err(EnvioError::InvalidState(msg)) => assert!(msg.contains("does not exist")),
+            other => panic!("Expected missing file error, got {:?}", other),
+        }
+
+        // Neither climbing out nor absolute paths nor symlinks reach files outside the directory
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("Outside.json"),
+            abi_dir.join("Linked.json"),
+        )
+        .unwrap();
+        for escaping in [
+            PathBuf::from("../Outside.json"),
+            temp_dir.path().join("Outside.json"),
+            PathBuf::from("Linked.json"),
+        ] {
+            contract.source = ContractSource::Abi {
+                abi: None,
+                url: None,
+                path: Some(escaping.clone()),
+            };
+            match manager.get_abi(&contract).await {
+                Err(EnvioError::InvalidState(msg)) => assert!(msg.contains("ABI directory")),
+                other => panic!("Expected {:?} to be refused, got {:?}", escaping, other),
+            }
+        }
+
+        // Without an ABI directory no path is read at all
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: None,
+            path: Some(PathBuf::from("Greeter.json")),
+        };
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf());
+        assert!(manager.get_abi(&contract).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_abi_fetch_not_found() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/missing.json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/missing.json", server.uri());
+        match fetch_abi_from_url(&url).await {
+            Err(EnvioError::AbiFetch {
+                url: failed_url,
+                status,
+            }) => {
+                assert_eq!(failed_url, url);
+                assert_eq!(status, 404);
+            }
+            other => panic!("Expected AbiFetch error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abis_fetched_concurrently() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let delay = std::time::Duration::from_millis(500);
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(crate::test_utils::GREETER_ABI)
+                    .set_delay(delay),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("abis")).unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf());
+        let contracts: Vec<ContractConfig> = (0..ABI_FETCH_CONCURRENCY)
+            .map(|i| {
+                let mut contract = create_test_contract(&format!("Greeter{}", i), "1");
+                contract.source = ContractSource::Abi {
+                    abi: None,
+                    url: Some(format!("{}/greeter{}.json", server.uri(), i)),
+                    path: None,
+                };
+                contract
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let failures = manager
+            .fetch_abis("concurrent", &contracts, temp_dir.path())
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(failures.is_empty());
+        // One after the other they would take a delay each
+        assert!(
+            elapsed < delay * 2,
+            "ABIs were fetched sequentially, took {:?}",
+            elapsed
+        );
+        for contract in &contracts {
+            let written =
+                std::fs::read_to_string(temp_dir.path().join(abi_file_path(&contract.name)))
+                    .unwrap();
+            assert_eq!(written, crate::test_utils::GREETER_ABI);
+        }
+    }
+
+    #[test]
+    fn test_ipfs_gateway_url() {
+        let cid = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+        assert_eq!(
+            ipfs_gateway_url(&format!("ipfs://{}", cid), DEFAULT_IPFS_GATEWAY),
+            Some(format!("https://ipfs.io/ipfs/{}", cid))
+        );
+        assert_eq!(
+            ipfs_gateway_url(&format!("ipfs/{}/abi.json", cid), "https://gateway.example"),
+            Some(format!("https://gateway.example/{}/abi.json", cid))
+        );
+        assert_eq!(
+            ipfs_gateway_url(&format!("ipfs://ipfs/{}", cid), DEFAULT_IPFS_GATEWAY),
+            Some(format!("https://ipfs.io/ipfs/{}", cid))
+        );
+        assert_eq!(ipfs_gateway_url("ipfs://", DEFAULT_IPFS_GATEWAY), None);
+        assert_eq!(
+            ipfs_gateway_url("https://example.com/abi.json", DEFAULT_IPFS_GATEWAY),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abi_from_explorer_tries_each_network_key() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // Only the Arbitrum key is accepted, requests without a key get a 404
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("apikey", "arbiscan_key"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(crate::test_utils::GREETER_ABI),
+            )
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf());
+        let mut contract = create_test_contract("Greeter", "1");
+        contract
+            .deployments
+            .push(crate::test_utils::create_deployment(
+                "42161",
+                Some("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1".into()),
+                None,
+                None,
+                None,
+            ));
+        contract.source = ContractSource::Explorer {
+            api_url: format!("{}/api", server.uri()),
+            api_keys: HashMap::from([("42161".to_string(), "arbiscan_key".to_string())]),
+        };
+        assert_eq!(
+            manager.get_abi(&contract).await.unwrap(),
+            crate::test_utils::GREETER_ABI
+        );
+    }
+
+    #[tokio::test]
+    async fn test_abi_from_ipfs_gateway() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ipfs/QmGreeter"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(crate::test_utils::GREETER_ABI),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/ipfs/QmReadme"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("# Not an ABI"))
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf())
+            .with_ipfs_gateway(format!("{}/ipfs/", server.uri()));
+        let mut contract = create_test_contract("Greeter", "1");
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: Some("ipfs://QmGreeter".to_string()),
+            path: None,
+        };
+        assert_eq!(
+            manager.get_abi(&contract).await.unwrap(),
+            crate::test_utils::GREETER_ABI
+        );
+
+        contract.source = ContractSource::Abi {
+            abi: None,
+            url: Some("ipfs://QmReadme".to_string()),
+            path: None,
+        };
+        match manager.get_abi(&contract).await {
+            Err(EnvioError::InvalidState(msg)) => assert!(msg.contains("not an ABI")),
+            other => panic!("Expected invalid ABI error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_indexer_status_serde_round_trip() {
+        let statuses = [
+            (IndexerStatus::Configured, r#"{"status":"Configured"}"#),
+            (IndexerStatus::Starting, r#"{"status":"Starting"}"#),
+            (IndexerStatus::Running, r#"{"status":"Running"}"#),
+            (
+                IndexerStatus::Failed("codegen failed".to_string()),
+                r#"{"status":"Failed","reason":"codegen failed"}"#,
+            ),
+            (IndexerStatus::Stopped, r#"{"status":"Stopped"}"#),
+        ];
+
+        for (status, json) in statuses {
+            assert_eq!(serde_json::to_string(&status).unwrap(), json);
+            let parsed: IndexerStatus = serde_json::from_str(json).unwrap();
+            assert_eq!(parsed, status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_pid() {
+        let mut project = EnvioProject {
+            id: "pid".to_string(),
+            dir: PathBuf::from("."),
+            process: None,
+        };
+        assert_eq!(project.pid(), None);
+
+        project.process = Some(Command::new("sleep").arg("30").spawn().unwrap());
+        assert!(project.pid().is_some_and(|pid| pid > 0));
+
+        project.process.as_mut().unwrap().kill().await.unwrap();
+        assert_eq!(project.pid(), None);
+
+        // A process that exits on its own stops reporting a pid as well
+        project.process = Some(Command::new("true").spawn().unwrap());
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert_eq!(project.pid(), None);
+    }
+
+    #[test]
+    fn test_apply_rpc_fallbacks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(
+            &config_path,
+            "name: fallback\nnetworks:\n- id: 1\n  start_block: 0\n  contracts: []\n",
+        )
+        .unwrap();
+
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].fallback_rpc_urls = vec![
+            "https://eth.llamarpc.com".to_string(),
+            "https://rpc.ankr.com/eth".to_string(),
+        ];
+        let config = IndexerConfig::new("fallback".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(
+            written["networks"][0]["hypersync_config"]["url"].as_str(),
+            Some("https://eth.hypersync.xyz")
+        );
+        let rpc = &written["networks"][0]["rpc"];
+        assert_eq!(rpc[0]["url"].as_str(), Some("https://eth.llamarpc.com"));
+        assert_eq!(rpc[0]["for"].as_str(), Some("fallback"));
+        assert_eq!(rpc[1]["url"].as_str(), Some("https://rpc.ankr.com/eth"));
+        assert!(rpc[0].get("ws").is_none());
+
+        // WebSocket URLs are attached to the HTTP entries instead of getting their own
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].fallback_rpc_urls = vec![
+            "wss://ethereum-rpc.publicnode.com".to_string(),
+            "https://eth.llamarpc.com".to_string(),
+        ];
+        let config = IndexerConfig::new("fallback".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let rpc = written["networks"][0]["rpc"].as_sequence().unwrap();
+        assert_eq!(rpc.len(), 1);
+        assert_eq!(rpc[0]["url"].as_str(), Some("https://eth.llamarpc.com"));
+        assert_eq!(
+            rpc[0]["ws"].as_str(),
+            Some("wss://ethereum-rpc.publicnode.com")
+        );
+
+        // Without HyperSync the deployment's own RPC is the one synced from
+        std::fs::write(
+            &config_path,
+            "name: fallback\nnetworks:\n- id: 999999\n  start_block: 0\n  contracts: []\n",
+        )
+        .unwrap();
+        let mut contract = create_test_contract("Token", "999999");
+        contract.deployments[0].rpc_url = "https://rpc.example.com".to_string();
+        contract.deployments[0].fallback_rpc_urls = vec!["https://backup.example.com".to_string()];
+        let config = IndexerConfig::new("fallback".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert!(written["networks"][0].get("hypersync_config").is_none());
+        let rpc = written["networks"][0]["rpc"].as_sequence().unwrap();
+        assert_eq!(rpc.len(), 2);
+        assert_eq!(rpc[0]["url"].as_str(), Some("https://rpc.example.com"));
+        assert_eq!(rpc[0]["for"].as_str(), Some("sync"));
+        assert_eq!(rpc[1]["url"].as_str(), Some("https://backup.example.com"));
+        assert_eq!(rpc[1]["for"].as_str(), Some("fallback"));
+
+        // A WebSocket primary is never written as an HTTP sync entry
+        let mut contract = create_test_contract("Token", "999999");
+        contract.deployments[0].rpc_url = "wss://rpc.example.com".to_string();
+        contract.deployments[0].fallback_rpc_urls = vec!["https://backup.example.com".to_string()];
+        let config = IndexerConfig::new("fallback".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let rpc = written["networks"][0]["rpc"].as_sequence().unwrap();
+        assert_eq!(rpc.len(), 1);
+        assert_eq!(rpc[0]["url"].as_str(), Some("https://backup.example.com"));
+        assert_eq!(rpc[0]["for"].as_str(), Some("fallback"));
+    }
+
+    #[test]
+    fn test_proxy_deployment_watches_proxy_address() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        let abi_path = format!("./{}", abi_file_path("Token").display());
+        std::fs::write(
+            &config_path,
+            format!(
+                "name: proxy\ncontracts:\n- name: Token\n  abi_file_path: {}\nnetworks:\n- id: 1\n  start_block: 0\n  contracts:\n  - name: Token\n    address:\n    - '0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045'\n",
+                abi_path
+            ),
+        )
+        .unwrap();
+
+        let mut contract = create_test_contract("Token", "1");
+        contract.deployments[0].proxy_address =
+            Some("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string());
+        let config = IndexerConfig::new("proxy".to_string(), vec![contract]);
+        apply_network_settings(&config_path, &config).unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let watched = &written["networks"][0]["contracts"][0];
+        assert_eq!(
+            watched["address"][0].as_str(),
+            Some("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D")
+        );
+        assert_eq!(watched["address"].as_sequence().unwrap().len(), 1);
+        // Events are still decoded with the implementation ABI
+        assert_eq!(
+            written["contracts"][0]["abi_file_path"].as_str(),
+            Some(abi_path.as_str())
+        );
+    }
+
+    #[test]
+    fn test_abi_file_paths_do_not_collide() {
+        let token = abi_file_path("Token");
+        let token2 = abi_file_path("Token2");
+        assert_ne!(token, token2);
+        assert!(token.starts_with("abis"));
+        assert_eq!(token, abi_file_path("Token"));
+
+        // Names that sanitize or fold to the same file name stay distinct
+        assert_ne!(abi_file_path("My Token"), abi_file_path("My_Token"));
+        assert_ne!(
+            abi_file_path("token").to_string_lossy().to_lowercase(),
+            abi_file_path("Token").to_string_lossy().to_lowercase()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_codegen_skipped_when_up_to_date() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let stub = write_stub_envio(temp_dir.path());
+
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf()).with_envio_binary(&stub);
+        let project = EnvioProject {
+            id: "codegen".to_string(),
+            dir: temp_dir.path().join("codegen"),
+            process: None,
+        };
+        std::fs::create_dir_all(&project.dir).unwrap();
+        std::fs::write(project.dir.join("config.yaml"), "name: codegen\n").unwrap();
+        let runs = || {
+            std::fs::read_to_string(project.dir.join("codegen_runs"))
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        assert!(manager.run_codegen_if_stale(&project, false).await.unwrap());
+        assert!(!manager.run_codegen_if_stale(&project, false).await.unwrap());
+        assert_eq!(runs(), 1);
+
+        std::fs::write(project.dir.join("config.yaml"), "name: changed\n").unwrap();
+        assert!(manager.run_codegen_if_stale(&project, false).await.unwrap());
+        assert_eq!(runs(), 2);
+
+        assert!(manager.run_codegen_if_stale(&project, true).await.unwrap());
+        assert_eq!(runs(), 3);
+    }
+
+    /// A contract whose ABI URL answers 404 next to a contract with an inline ABI
+    async fn contracts_with_missing_abi(server: &wiremock::MockServer) -> Vec<ContractConfig> {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        Mock::given(method("GET"))
+            .and(path("/missing.json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(server)
+            .await;
+
+        let mut broken = create_test_contract("Broken", "1");
+        broken.source = ContractSource::Abi {
+            abi: None,
+            url: Some(format!("{}/missing.json", server.uri())),
+            path: None,
+        };
+        vec![create_test_contract("Greeter", "1"), broken]
+    }
+
+    #[tokio::test]
+    async fn test_init_aborts_on_abi_failure() {
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf())
+            .with_envio_binary(write_stub_envio(temp_dir.path()));
+
+        let contracts = contracts_with_missing_abi(&server).await;
+        match manager.init_project("aborted", contracts).await {
+            Err(EnvioError::AbiUnavailable(failures)) => {
+                assert_eq!(failures.len(), 1);
+                assert!(failures[0].starts_with("Broken:"));
+            }
+            other => panic!("Expected AbiUnavailable error, got {:?}", other),
+        }
+        // The partial project would otherwise be reused by a later spawn
+        assert!(!temp_dir.path().join("aborted").exists());
+
+        // A failed re-init leaves the existing project in place
+        std::fs::create_dir_all(temp_dir.path().join("existing")).unwrap();
+        let contracts = contracts_with_missing_abi(&server).await;
+        assert!(manager.init_project("existing", contracts).await.is_err());
+        assert!(temp_dir.path().join("existing").exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_init_skips_failed_contracts() {
+        let server = wiremock::MockServer::start().await;
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf())
+            .with_envio_binary(write_stub_envio(temp_dir.path()));
+
+        let contracts = contracts_with_missing_abi(&server).await;
+        let initialized = manager
+            .init_project_cancellable(
+                "skipped",
+                contracts,
+                Ecosystem::Evm,
+                true,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("Init should proceed without the broken contract");
+        assert_eq!(initialized.skipped_contracts, vec!["Broken".to_string()]);
+
+        let project_dir = initialized.project.dir;
+        assert!(project_dir.join(abi_file_path("Greeter")).exists());
+        assert!(!project_dir.join(abi_file_path("Broken")).exists());
+
+        // The project is only reused for the contracts it was initialized with
+        let contracts = contracts_with_missing_abi(&server).await;
+        assert!(!manager.project_matches("skipped", &contracts, Ecosystem::Evm));
+        assert!(manager.project_matches("skipped", &contracts[..1], Ecosystem::Evm));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_slow_log_consumer_drops_lines() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manager = EnvioManager::new(temp_dir.path().to_path_buf());
+        // Far more output than the pipe buffer holds, so a blocked reader would stall the process
+        let mut project = EnvioProject {
+            id: "chatty".to_string(),
+            dir: temp_dir.path().to_path_buf(),
+            process: Some(
+                Command::new("seq")
+                    .args(["1", "200000"])
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped())
+                    .spawn()
+                    .unwrap(),
+            ),
+        };
+        let mut rx = manager.subscribe_to_logs(&mut project).unwrap();
+
+        // Nothing is consumed until the process is done
+        let child = project.process.as_mut().unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(30), child.wait())
+            .await
+            .expect("A slow consumer must not block the process output")
+            .unwrap();
+
+        let mut lines = 0;
+        let mut marker = None;
+        while let Ok(Some(msg)) =
+            tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await
+        {
+            match msg {
+                IndexerLogMessage::Stdout(line) if line.ends_with("lines dropped]") => {
+                    marker = Some(line)
+                }
+                IndexerLogMessage::Stdout(_) => lines += 1,
+                _ => {}
+            }
+        }
+        assert!(lines <= LOG_CHANNEL_CAPACITY);
+        let dropped: usize = marker
+            .expect("Dropped lines should be reported")
+            .trim_start_matches('[')
+            .split(' ')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(lines + dropped, 200000);
+    }
+}
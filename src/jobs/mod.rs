@@ -1,3 +1,4 @@
+use crate::envio_utils::IndexerStatus;
 use crate::service_context::SpawnIndexerParams;
 use blueprint_sdk::event_listeners::tangle::{
     events::TangleEventListener, services::services_pre_processor,
@@ -19,17 +20,40 @@ pub async fn spawn_indexer_local(
     params: Vec<u8>,
     context: ServiceContext,
 ) -> Result<Vec<u8>, String> {
-    let params = serde_json::from_slice::<SpawnIndexerParams>(&params)
-        .map_err(|e| format!("Failed to parse params: {}", e))?;
+    let mut params = SpawnIndexerParams::from_slice(&params)?;
 
     // Validate the configuration
+    params.config.normalize_addresses()?;
+    params.config.dedup_deployments()?;
     params.config.validate()?;
 
-    // Use existing EnvioManager implementation
-    let result = context.spawn_indexer(params.config).await?;
+    // Conflicts are resolved here, so they no longer show up in the spawned config's warnings
+    let rpc_warnings: Vec<String> = params
+        .config
+        .normalize_rpc_urls()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
 
-    // Start the indexer
-    let result = context.start_indexer(&result.id).await?;
+    let mut result = match &params.idempotency_key {
+        // Without a key every call is a fresh indexer, rolled back if it fails to start
+        None => context.spawn_and_start(params.config, None).await?,
+        Some(key) => {
+            let result = context.spawn_indexer_idempotent(params.config, key).await?;
+
+            // A retried spawn may find the indexer already running. One that only claims to be,
+            // because its start failed before a process came up, is started again
+            let status = context.get_indexer_status(&result.id).await?;
+            if matches!(status, IndexerStatus::Starting | IndexerStatus::Running)
+                && context.has_live_instance(&result.id).await?
+            {
+                result
+            } else {
+                context.start_indexer(&result.id).await?
+            }
+        }
+    };
+    result.warnings.splice(0..0, rpc_warnings);
 
     serde_json::to_vec(&result).map_err(|e| format!("Failed to serialize result: {}", e))
 }
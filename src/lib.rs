@@ -1,8 +1,12 @@
+pub mod address;
 pub mod envio_utils;
 pub mod jobs;
 pub mod network;
 pub mod service_context;
 pub mod test_utils;
 
+pub use envio_utils::indexer_config_schema;
+pub use service_context::spawn_indexer_params_schema;
+
 #[cfg(test)]
 mod tests;
@@ -1,5 +1,9 @@
 use blueprint_sdk::runners::{core::runner::BlueprintRunner, tangle::tangle::TangleConfig};
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
+use envio_hyperindex_blueprint::envio_utils::IPFS_GATEWAY_ENV;
+use envio_hyperindex_blueprint::jobs::{
+    SpawnIndexerLocalEventHandler, StopIndexerLocalEventHandler,
+};
 use envio_hyperindex_blueprint::service_context::ServiceContext;
 
 #[blueprint_sdk::main(env)]
@@ -8,12 +12,30 @@ async fn main() -> Result<()> {
         .clone()
         .data_dir
         .map(|dir| dir.join("indexers"))
-        .unwrap_or_default();
-    let _context = ServiceContext::new(env.clone(), base_dir);
+        .ok_or_else(|| eyre!("No data_dir configured, set one to store indexer projects"))?;
+    // Resolved once, before anything can change the working directory
+    let base_dir = std::path::absolute(&base_dir)
+        .map_err(|e| eyre!("Failed to resolve data_dir {:?}: {}", base_dir, e))?;
+    let mut builder = ServiceContext::builder(env.clone()).base_dir(base_dir);
+    if let Some(ipfs_gateway) = std::env::var(IPFS_GATEWAY_ENV)
+        .ok()
+        .filter(|gateway| !gateway.is_empty())
+    {
+        builder = builder.ipfs_gateway(ipfs_gateway);
+    }
+    let context = builder.build().map_err(|e| eyre!(e))?;
+
+    // Every handler gets a clone of the same context, sharing its indexer state
+    let spawn_indexer = SpawnIndexerLocalEventHandler::new(&env, context.clone()).await?;
+    let stop_indexer = StopIndexerLocalEventHandler::new(&env, context).await?;
 
     blueprint_sdk::logging::info!("Starting the event watcher ...");
     let tangle_config = TangleConfig::default();
-    BlueprintRunner::new(tangle_config, env).run().await?;
+    BlueprintRunner::new(tangle_config, env)
+        .job(spawn_indexer)
+        .job(stop_indexer)
+        .run()
+        .await?;
 
     blueprint_sdk::logging::info!("Exiting...");
     Ok(())
@@ -13,6 +13,34 @@ pub fn supported_network_ids() -> Vec<u64> {
     SUPPORTED_NETWORKS.keys().cloned().collect()
 }
 
+/// Environment variable envio reads its HyperSync API token from
+pub const HYPERSYNC_TOKEN_ENV: &str = "ENVIO_API_TOKEN";
+
+/// Returns the HyperSync API token from the environment, if set
+pub fn hypersync_api_token() -> Option<String> {
+    std::env::var(HYPERSYNC_TOKEN_ENV)
+        .ok()
+        .filter(|token| !token.is_empty())
+}
+
+/// Returns the HyperSync URL of a network
+///
+/// The URL carries no token, envio authenticates with the one in [`HYPERSYNC_TOKEN_ENV`].
+pub fn hypersync_url(network_id: u64) -> Result<String, String> {
+    Ok(validate_network(network_id)?.rpc_url.clone())
+}
+
+/// Fuel networks as `(id, name)`, in the order `envio init` lists them
+pub const FUEL_NETWORKS: [(u64, &str); 2] = [(9889, "mainnet"), (0, "testnet")];
+
+/// Position of a Fuel network, given by id or name, in [`FUEL_NETWORKS`]
+pub fn fuel_network_position(network: &str) -> Option<usize> {
+    let network = network.trim();
+    FUEL_NETWORKS.iter().position(|(id, name)| {
+        network.parse::<u64>().ok() == Some(*id) || network.eq_ignore_ascii_case(name)
+    })
+}
+
 /// Returns all networks that support traces
 pub fn networks_with_traces() -> Vec<&'static NetworkInfo> {
     SUPPORTED_NETWORKS
@@ -34,6 +62,20 @@ mod tests {
         assert!(validate_network(999999).is_err());
     }
 
+    #[test]
+    fn test_hypersync_url() {
+        assert_eq!(hypersync_url(1).unwrap(), "https://eth.hypersync.xyz");
+        assert!(hypersync_url(999999).is_err());
+    }
+
+    #[test]
+    fn test_fuel_network_position() {
+        assert_eq!(fuel_network_position("9889"), Some(0));
+        assert_eq!(fuel_network_position("Testnet"), Some(1));
+        assert_eq!(fuel_network_position("0"), Some(1));
+        assert_eq!(fuel_network_position("1"), None);
+    }
+
     #[test]
     fn test_networks_with_traces() {
         let trace_networks = networks_with_traces();
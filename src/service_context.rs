@@ -1,24 +1,98 @@
 use crate::envio_utils::project::IndexerProgress;
 use crate::envio_utils::project::IndexerStatus;
-use crate::envio_utils::{self, EnvioManager, EnvioProject, IndexerConfig, IndexerLogMessage};
+use crate::envio_utils::{
+    self, ConfigFormat, EnvioDocker, EnvioDockerConfig, EnvioManager, EnvioProject,
+    HealthCheckConfig, IndexerConfig, IndexerLogMessage, LogRotationConfig, RotatingLogFile,
+    SpawnProgress, DEFAULT_GRAPHQL_PORT, INDEXER_LOG_FILE,
+};
 use blueprint_sdk::config::GadgetConfiguration;
+use blueprint_sdk::logging::{debug, info, warn};
 use blueprint_sdk::macros::contexts::ServicesContext;
 use blueprint_sdk::macros::contexts::TangleClientContext;
+use blueprint_sdk::std::collections::hash_map::Entry;
 use blueprint_sdk::std::collections::HashMap;
-use blueprint_sdk::std::path::PathBuf;
+use blueprint_sdk::std::path::{Path, PathBuf};
 use blueprint_sdk::std::sync::Arc;
 use blueprint_sdk::tokio;
 use blueprint_sdk::tokio::process::Child;
 use blueprint_sdk::tokio::sync::RwLock;
 use schemars::JsonSchema;
 
-use blueprint_sdk::tokio::sync::mpsc;
+use blueprint_sdk::tokio::sync::{broadcast, mpsc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Current version of the [`SpawnIndexerParams`] payload
+pub const SPAWN_PARAMS_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SpawnIndexerParams {
+    /// Payload version, absent (0) for payloads predating versioning
+    #[serde(default)]
+    pub version: u32,
     pub config: IndexerConfig,
+    /// Caller-supplied key making retried spawns return the same indexer
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+
+impl SpawnIndexerParams {
+    pub fn new(config: IndexerConfig) -> Self {
+        Self {
+            version: SPAWN_PARAMS_VERSION,
+            config,
+            idempotency_key: None,
+        }
+    }
+
+    /// Parses job params, migrating payloads from older versions to the current one
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        let mut value: serde_json::Value =
+            serde_json::from_slice(bytes).map_err(|e| format!("Failed to parse params: {}", e))?;
+
+        let version = match value.get("version") {
+            None => 0,
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| format!("Invalid params version: {}", v))?,
+        };
+        if version > SPAWN_PARAMS_VERSION {
+            return Err(format!(
+                "Unsupported params version {}, the latest supported version is {}",
+                version, SPAWN_PARAMS_VERSION
+            ));
+        }
+
+        // v0 payloads could also be a bare `IndexerConfig`
+        if version == 0 && value.get("config").is_none() && value.get("contracts").is_some() {
+            value = serde_json::json!({ "config": value });
+        }
+
+        let mut params: Self = serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse params (version {}): {}", version, e))?;
+        params.version = SPAWN_PARAMS_VERSION;
+        Ok(params)
+    }
+}
+
+/// JSON Schema of the `spawn_indexer` job parameters
+pub fn spawn_indexer_params_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(SpawnIndexerParams))
+        .expect("JSON Schema is always serializable")
+}
+
+/// Files a spawn would generate for a config, see [`ServiceContext::dry_run`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DryRunReport {
+    /// The envio `config.yaml`
+    pub config_yaml: String,
+    /// The GraphQL schema of the contracts' events
+    pub schema: String,
+    /// Non-fatal problems found in the indexer's config
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,6 +101,15 @@ pub struct SpawnIndexerResult {
     pub id: String,
     /// Status message
     pub message: String,
+    /// Non-fatal problems found in the indexer's config
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// GraphQL endpoint of the running indexer
+    #[serde(default)]
+    pub graphql_endpoint: Option<String>,
+    /// Hasura console of the running indexer
+    #[serde(default)]
+    pub hasura_console: Option<String>,
 }
 
 pub struct IndexerProcess {
@@ -37,11 +120,120 @@ pub struct IndexerProcess {
     pub status: IndexerStatus,
     pub logs: Vec<String>,
     pub last_checked: std::time::Instant,
+    /// Latest reported progress, keyed by chain id
+    pub progress: HashMap<String, IndexerProgress>,
+    /// Consecutive health checks that found the indexer unhealthy
+    pub health_failures: u32,
+    /// Fan-out of the log stream, attached when the indexer starts
+    pub log_broadcast: Option<LogBroadcast>,
+    /// Endpoint checked by `monitor_indexer` for a locally running indexer
+    pub health_check: HealthCheckConfig,
+    /// Process adopted by `attach_existing_process`, which this context only knows by PID
+    pub attached_pid: Option<u32>,
+    /// GraphQL endpoint the running indexer announced in its logs
+    pub graphql_endpoint: Option<String>,
+    /// Config as passed to the spawn, which idempotent retries must repeat. `None` if imported
+    pub spawn_config: Option<IndexerConfig>,
+}
+
+/// Maximum number of log lines kept in memory per indexer
+pub const MAX_IN_MEMORY_LOG_LINES: usize = 1000;
+
+/// Number of recent log messages `tail_logs` can replay to a new subscriber
+pub const LOG_TAIL_CAPACITY: usize = 200;
+
+/// Default number of consecutive failed health checks before an indexer is marked `Failed`
+pub const DEFAULT_MAX_HEALTH_FAILURES: u32 = 10;
+
+/// Base interval between health checks, doubled after each failed check
+const HEALTH_CHECK_BASE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Upper bound for the backed-off health check interval
+const HEALTH_CHECK_MAX_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl IndexerProcess {
+    /// Time to wait after `last_checked` before the next health check
+    pub fn next_health_check_interval(&self) -> std::time::Duration {
+        HEALTH_CHECK_BASE_INTERVAL
+            .saturating_mul(2u32.saturating_pow(self.health_failures))
+            .min(HEALTH_CHECK_MAX_INTERVAL)
+    }
+
+    /// Applies the status observed by a health check.
+    ///
+    /// A process that exited cleanly is stopped and one that exited with an error fails the
+    /// indexer immediately. Misses don't count while the indexer is still starting up. Once it
+    /// has run, a live but unhealthy process is only failed after `max_failures` consecutive
+    /// misses, and a healthy check resets the count.
+    pub fn record_health_check(&mut self, observed: IndexerStatus, max_failures: u32) {
+        self.status = match observed {
+            IndexerStatus::Running => {
+                self.health_failures = 0;
+                IndexerStatus::Running
+            }
+            IndexerStatus::Starting if self.status == IndexerStatus::Starting => {
+                IndexerStatus::Starting
+            }
+            IndexerStatus::Starting => {
+                self.health_failures += 1;
+                if self.health_failures >= max_failures {
+                    IndexerStatus::Failed(format!(
+                        "Health check failed {} times in a row",
+                        self.health_failures
+                    ))
+                } else {
+                    // Still considered running while below the threshold
+                    self.status.clone()
+                }
+            }
+            IndexerStatus::Stopped | IndexerStatus::Configured => IndexerStatus::Stopped,
+            failed @ IndexerStatus::Failed(_) => failed,
+        };
+    }
+
+    /// Appends a log line, discarding the oldest lines beyond [`MAX_IN_MEMORY_LOG_LINES`]
+    pub fn push_log(&mut self, line: String) {
+        self.logs.push(line);
+        if self.logs.len() > MAX_IN_MEMORY_LOG_LINES {
+            let excess = self.logs.len() - MAX_IN_MEMORY_LOG_LINES;
+            self.logs.drain(..excess);
+        }
+    }
+}
+
+/// Buffered status transitions per [`ServiceContext::subscribe_events`] subscriber
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// A status transition of an indexer
+#[derive(Debug, Clone)]
+pub struct IndexerEvent {
+    pub id: String,
+    pub old_status: IndexerStatus,
+    pub new_status: IndexerStatus,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+}
+
+/// Aggregated health of all indexers managed by a [`ServiceContext`]
+#[derive(Debug, Clone, Default)]
+pub struct HealthSummary {
+    pub total: usize,
+    pub configured: usize,
+    pub starting: usize,
+    pub running: usize,
+    pub failed: usize,
+    pub stopped: usize,
+    /// Status of each indexer, keyed by id
+    pub indexers: HashMap<String, IndexerStatus>,
+    /// OS process id of each indexer running as a local process, keyed by id
+    pub pids: HashMap<String, u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum DeploymentMode {
+    /// Run `envio dev` as a local subprocess
     Local,
+    /// Run the indexer and its Postgres database in local Docker containers
+    Docker,
 }
 
 #[derive(Clone, ServicesContext, TangleClientContext)]
@@ -52,40 +244,279 @@ pub struct ServiceContext {
     pub call_id: Option<u64>,
     pub indexers: Arc<RwLock<HashMap<String, IndexerProcess>>>,
     pub envio_manager: Arc<EnvioManager>,
+    pub envio_docker: Option<Arc<EnvioDocker>>,
     pub deployment_mode: DeploymentMode,
+    /// Rotation settings for persisted indexer logs, `None` disables persistence
+    pub log_rotation: Option<LogRotationConfig>,
+    /// Consecutive failed health checks before a live indexer is marked `Failed`
+    pub max_health_failures: u32,
+    /// Run `envio codegen` on every start, even when the project is unchanged
+    pub force_codegen: bool,
+    /// Cancellation tokens of spawns that are still initializing, keyed by indexer id
+    pub pending_spawns: Arc<RwLock<HashMap<String, CancellationToken>>>,
+    events_tx: broadcast::Sender<IndexerEvent>,
 }
 
 impl ServiceContext {
-    pub fn new(config: GadgetConfiguration, data_dir: PathBuf) -> Self {
-        Self {
-            config,
-            call_id: None,
-            indexers: Arc::new(RwLock::new(HashMap::new())),
-            envio_manager: Arc::new(EnvioManager::new(data_dir)),
-            deployment_mode: DeploymentMode::Local,
+    /// Start building a context with the given blueprint configuration
+    pub fn builder(config: GadgetConfiguration) -> ServiceContextBuilder {
+        ServiceContextBuilder::new(config)
+    }
+
+    /// Create a context storing indexer projects under `data_dir`
+    ///
+    /// An empty or relative `data_dir` is rejected.
+    pub fn new(config: GadgetConfiguration, data_dir: PathBuf) -> Result<Self, String> {
+        Self::builder(config).base_dir(data_dir).build()
+    }
+
+    /// Create a context that runs indexers in Docker containers
+    pub fn new_docker(
+        config: GadgetConfiguration,
+        data_dir: PathBuf,
+        docker_config: EnvioDockerConfig,
+    ) -> Result<Self, String> {
+        Self::builder(config)
+            .base_dir(data_dir)
+            .docker(docker_config)
+            .build()
+    }
+
+    /// Checks that indexer projects can be created in the base directory, creating it if needed
+    ///
+    /// Read-only or foreign-owned volumes would otherwise only fail deep inside a spawn.
+    pub fn check_writable(&self) -> Result<(), String> {
+        let base_dir = self.envio_manager.base_dir();
+        let unwritable = |e: std::io::Error| {
+            format!(
+                "Indexer directory {} is not writable ({}), make sure it is mounted read-write \
+                 and owned by the user running the blueprint",
+                base_dir.display(),
+                e
+            )
+        };
+
+        std::fs::create_dir_all(base_dir).map_err(unwritable)?;
+        let probe = base_dir.join(format!(".write_probe_{}", uuid::Uuid::new_v4()));
+        std::fs::write(&probe, b"").map_err(unwritable)?;
+        std::fs::remove_file(&probe).map_err(unwritable)
+    }
+
+    /// Subscribes to the status transitions of every indexer
+    ///
+    /// A subscriber that falls more than [`EVENT_CHANNEL_CAPACITY`] events behind misses the
+    /// oldest ones.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<IndexerEvent> {
+        self.events_tx.subscribe()
+    }
+
+    /// Moves `process` to `status`, notifying event subscribers if that changes it
+    fn set_status(&self, process: &mut IndexerProcess, status: IndexerStatus) {
+        let old_status = std::mem::replace(&mut process.status, status);
+        self.notify_transition(&process.id, old_status, process.status.clone());
+    }
+
+    fn notify_transition(&self, id: &str, old_status: IndexerStatus, new_status: IndexerStatus) {
+        if old_status == new_status {
+            return;
+        }
+        // Nobody listening is not an error
+        let _ = self.events_tx.send(IndexerEvent {
+            id: id.to_string(),
+            old_status,
+            new_status,
+            timestamp: chrono::Local::now(),
+        });
+    }
+
+    /// GraphQL endpoint and Hasura console URLs for a started indexer
+    ///
+    /// Docker indexers are published on a host port of their own, so there are no shared
+    /// endpoints for them, see [`ServiceContext::graphql_endpoint`].
+    pub fn endpoints(&self) -> (Option<String>, Option<String>) {
+        match self.deployment_mode {
+            DeploymentMode::Local => endpoints_on(DEFAULT_GRAPHQL_PORT),
+            DeploymentMode::Docker => (None, None),
         }
     }
 
-    fn generate_indexer_id(&self, name: &str) -> String {
-        let id = uuid::Uuid::new_v4();
+    fn docker(&self) -> Result<&Arc<EnvioDocker>, String> {
+        self.envio_docker
+            .as_ref()
+            .ok_or_else(|| "Docker deployment mode requires an EnvioDocker client".to_string())
+    }
+
+    async fn generate_indexer_id(&self, name: &str) -> String {
+        let indexers = self.indexers.read().await;
+        self.unique_indexer_id(&indexers, name, || uuid::Uuid::new_v4().to_string())
+    }
+
+    /// Builds `indexer_<name>_<suffix>` ids until one is neither registered nor has a project on disk
+    pub(crate) fn unique_indexer_id(
+        &self,
+        indexers: &HashMap<String, IndexerProcess>,
+        name: &str,
+        mut next_suffix: impl FnMut() -> String,
+    ) -> String {
         let name = name.to_lowercase().replace([' ', '-'], "_");
-        format!("indexer_{}_{}", name, id)
+        loop {
+            let id = format!("indexer_{}_{}", name, next_suffix());
+            if !indexers.contains_key(&id) && !self.envio_manager.base_dir().join(&id).exists() {
+                return id;
+            }
+        }
+    }
+
+    /// Deterministic indexer id derived from the config name and an idempotency key
+    ///
+    /// The key is hashed rather than sanitized, so keys like `call-42` and `call_42` stay apart.
+    pub fn idempotent_indexer_id(name: &str, idempotency_key: &str) -> String {
+        use sha3::{Digest, Keccak256};
+
+        let name: String = name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let key_hash: String = Keccak256::digest(idempotency_key.as_bytes())[..16]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("indexer_{}_{}", name, key_hash)
     }
 
     pub async fn spawn_indexer(&self, config: IndexerConfig) -> Result<SpawnIndexerResult, String> {
-        let id = self.generate_indexer_id(&config.name);
+        let id = self.generate_indexer_id(&config.name).await;
+        self.cancellable_spawn(&id.clone(), |cancel| async move {
+            self.spawn_indexer_with_id(id, config, false, &cancel).await
+        })
+        .await
+    }
+
+    /// Spawn an indexer, or return the existing one if this key was already used
+    pub async fn spawn_indexer_idempotent(
+        &self,
+        config: IndexerConfig,
+        idempotency_key: &str,
+    ) -> Result<SpawnIndexerResult, String> {
+        let id = Self::idempotent_indexer_id(&config.name, idempotency_key);
+        self.cancellable_spawn(&id.clone(), |cancel| async move {
+            self.spawn_indexer_with_id(id, config, true, &cancel).await
+        })
+        .await
+    }
+
+    /// Runs `spawn` with a token that [`Self::cancel_spawn`] fires for `id`
+    async fn cancellable_spawn<T, F>(
+        &self,
+        id: &str,
+        spawn: impl FnOnce(CancellationToken) -> F,
+    ) -> Result<T, String>
+    where
+        F: std::future::Future<Output = Result<T, String>>,
+    {
+        let cancel = CancellationToken::new();
+        self.pending_spawns
+            .write()
+            .await
+            .insert(id.to_string(), cancel.clone());
+        let result = spawn(cancel).await;
+        self.pending_spawns.write().await.remove(id);
+        result
+    }
+
+    async fn spawn_indexer_with_id(
+        &self,
+        id: String,
+        mut config: IndexerConfig,
+        reuse_existing: bool,
+        cancel: &CancellationToken,
+    ) -> Result<SpawnIndexerResult, String> {
+        // Addresses are stored and compared in their checksummed form, however they were given
+        config.normalize_addresses()?;
+        config.dedup_deployments()?;
+        let spawn_config = config.clone();
+
+        // Start from the contract creation where the explorer knows it, rather than genesis. The
+        // lookup goes first, so a slow explorer doesn't hold the lock on every other indexer
+        let mut warnings = config.resolve_start_blocks().await;
+        if cancel.is_cancelled() {
+            return Err(envio_utils::EnvioError::Cancelled.to_string());
+        }
+
+        // The id is checked and claimed under the same lock, so concurrent spawns can't both take it
         let mut indexers = self.indexers.write().await;
 
-        if indexers.contains_key(&id) {
-            return Err(format!("Indexer with id {} already exists", id));
+        let slot = match indexers.entry(id.clone()) {
+            Entry::Vacant(slot) => slot,
+            Entry::Occupied(existing) => {
+                let existing = existing.get();
+                if !reuse_existing {
+                    return Err(format!("Indexer with id {} already exists", id));
+                }
+                if existing.spawn_config.as_ref() != Some(&spawn_config) {
+                    return Err(format!(
+                        "Indexer {} was already spawned with this idempotency key and a different config",
+                        id
+                    ));
+                }
+                return Ok(SpawnIndexerResult {
+                    warnings: existing.config.validation_warnings(),
+                    id,
+                    message: "Indexer already exists".to_string(),
+                    graphql_endpoint: None,
+                    hasura_console: None,
+                });
+            }
+        };
+
+        // A project left behind by an earlier spawn (e.g. one that crashed while starting) is
+        // reused, unless it was created for a different config
+        let reused_project =
+            self.envio_manager
+                .project_matches(&id, &config.contracts, config.ecosystem);
+        let project_dir = self.envio_manager.base_dir().join(&id);
+        if !reused_project && project_dir.exists() {
+            info!(
+                "Project of indexer {} doesn't match its config, re-initializing it",
+                id
+            );
+            std::fs::remove_dir_all(&project_dir)
+                .map_err(|e| format!("Failed to remove stale project of {}: {}", id, e))?;
         }
+        let project = if reused_project {
+            info!("Reusing existing project for indexer {}", id);
+            EnvioProject {
+                id: id.clone(),
+                dir: project_dir,
+                process: None,
+            }
+        } else {
+            // Initialize envio project with all contracts
+            let initialized = self
+                .envio_manager
+                .init_project_cancellable(
+                    &id,
+                    config.clone().contracts,
+                    config.ecosystem,
+                    config.skip_failed_contracts,
+                    cancel,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
 
-        // Initialize envio project with all contracts
-        let project = self
-            .envio_manager
-            .init_project(&id, config.clone().contracts)
-            .await
-            .map_err(|e| e.to_string())?;
+            // The stored config only keeps the contracts the project was created with
+            config
+                .contracts
+                .retain(|contract| !initialized.skipped_contracts.contains(&contract.name));
+            warnings.extend(initialized.skipped_contracts.iter().map(|name| {
+                format!(
+                    "Contract {} was skipped because its ABI could not be fetched",
+                    name
+                )
+            }));
+            initialized.project
+        };
 
         // Create indexer process entry with new fields
         let process = IndexerProcess {
@@ -96,32 +527,379 @@ impl ServiceContext {
             status: IndexerStatus::Configured,
             logs: vec![format!("[{}] Indexer created", chrono::Local::now())],
             last_checked: std::time::Instant::now(),
+            progress: HashMap::new(),
+            health_failures: 0,
+            log_broadcast: None,
+            health_check: HealthCheckConfig::default(),
+            attached_pid: None,
+            graphql_endpoint: None,
+            spawn_config: Some(spawn_config),
+        };
+
+        slot.insert(process);
+        let message = if reused_project {
+            "Indexer spawned from existing project"
+        } else {
+            "Indexer spawned successfully"
+        };
+        warnings.extend(config.validation_warnings());
+        Ok(SpawnIndexerResult {
+            id,
+            message: message.to_string(),
+            warnings,
+            graphql_endpoint: None,
+            hasura_console: None,
+        })
+    }
+
+    /// Registers an existing envio project directory as a `Configured` indexer
+    ///
+    /// The project is used in place, so it must stay where it is while the indexer is managed.
+    pub async fn import_project(
+        &self,
+        path: PathBuf,
+        name: String,
+    ) -> Result<SpawnIndexerResult, String> {
+        if !path.join("config.yaml").is_file() {
+            return Err(format!("No envio config.yaml found in {:?}", path));
+        }
+        let path = path
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve project directory {:?}: {}", path, e))?;
+
+        // Best effort, the project keeps running off its own config.yaml either way
+        let contracts = match std::fs::read_to_string(path.join("config.yaml"))
+            .map_err(envio_utils::EnvioError::from)
+            .and_then(|yaml| envio_utils::parse_envio_config(&yaml))
+        {
+            Ok(parsed) => parsed
+                .contracts
+                .into_iter()
+                .map(|mut contract| {
+                    // ABI paths in config.yaml are relative to the project, which is not the
+                    // ABI directory paths are read from later, so the ABI is taken inline
+                    if let envio_utils::ContractSource::Abi {
+                        path: Some(abi_path),
+                        ..
+                    } = &contract.source
+                    {
+                        contract.source = match read_project_abi(&path, abi_path) {
+                            Ok(abi) => envio_utils::ContractSource::Abi {
+                                abi: Some(abi),
+                                url: None,
+                                path: None,
+                            },
+                            Err(e) => {
+                                warn!("Failed to read ABI of {}: {}", contract.name, e);
+                                envio_utils::ContractSource::Inferred
+                            }
+                        };
+                    }
+                    contract
+                })
+                .collect(),
+            Err(e) => {
+                warn!("Failed to parse config.yaml of {:?}: {}", path, e);
+                Vec::new()
+            }
+        };
+        let config = IndexerConfig::new(name, contracts);
+        let id = self.generate_indexer_id(&config.name).await;
+
+        let process = IndexerProcess {
+            id: id.clone(),
+            config,
+            output_dir: path.clone(),
+            process: None,
+            status: IndexerStatus::Configured,
+            logs: vec![format!(
+                "[{}] Indexer imported from {:?}",
+                chrono::Local::now(),
+                path
+            )],
+            last_checked: std::time::Instant::now(),
+            progress: HashMap::new(),
+            health_failures: 0,
+            log_broadcast: None,
+            health_check: HealthCheckConfig::default(),
+            attached_pid: None,
+            graphql_endpoint: None,
+            spawn_config: None,
         };
+        self.indexers.write().await.insert(id.clone(), process);
 
-        indexers.insert(id.clone(), process);
         Ok(SpawnIndexerResult {
             id,
-            message: "Indexer spawned successfully".to_string(),
+            message: "Indexer imported successfully".to_string(),
+            warnings: Vec::new(),
+            graphql_endpoint: None,
+            hasura_console: None,
+        })
+    }
+
+    /// Aborts a spawn that is still initializing or starting its indexer
+    ///
+    /// The cancelled spawn kills `envio init` or `envio codegen`, removes its partial project and
+    /// returns an error. A spawn that already started its indexer is rolled back.
+    pub async fn cancel_spawn(&self, id: &str) -> Result<(), String> {
+        let pending_spawns = self.pending_spawns.read().await;
+        let cancel = pending_spawns
+            .get(id)
+            .ok_or_else(|| format!("No spawn in progress for indexer {}", id))?;
+        cancel.cancel();
+        Ok(())
+    }
+
+    /// Spawns and starts an indexer, optionally waiting up to `wait` for it to run
+    ///
+    /// If starting or waiting fails, the indexer is stopped and its entry and project removed.
+    pub async fn spawn_and_start(
+        &self,
+        config: IndexerConfig,
+        wait: Option<std::time::Duration>,
+    ) -> Result<SpawnIndexerResult, String> {
+        let id = self.generate_indexer_id(&config.name).await;
+        self.spawn_and_start_with_id(id, config, wait).await
+    }
+
+    /// Like [`Self::spawn_and_start`], but sends each stage the spawn reaches to `progress`
+    ///
+    /// Every stage reached is sent before this returns, also when the spawn fails.
+    pub async fn spawn_and_start_with_progress(
+        &self,
+        config: IndexerConfig,
+        wait: Option<std::time::Duration>,
+        progress: mpsc::UnboundedSender<SpawnProgress>,
+    ) -> Result<SpawnIndexerResult, String> {
+        let id = self.generate_indexer_id(&config.name).await;
+
+        // Subscribe before spawning so no stage is missed
+        let mut events = self.envio_manager.stream_init_progress();
+        let (done_tx, mut done_rx) = tokio::sync::oneshot::channel::<()>();
+        let forward_id = id.clone();
+        let forwarder = tokio::spawn(async move {
+            let forward = |event: envio_utils::InitProgressEvent| {
+                if event.id == forward_id {
+                    let _ = progress.send(event.progress);
+                }
+            };
+            loop {
+                tokio::select! {
+                    biased;
+                    event = events.recv() => match event {
+                        Ok(event) => forward(event),
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = &mut done_rx => {
+                        // Drain whatever was reported before the spawn returned
+                        while let Ok(event) = events.try_recv() {
+                            forward(event);
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        let result = self.spawn_and_start_with_id(id, config, wait).await;
+        let _ = done_tx.send(());
+        let _ = forwarder.await;
+        result
+    }
+
+    async fn spawn_and_start_with_id(
+        &self,
+        id: String,
+        config: IndexerConfig,
+        wait: Option<std::time::Duration>,
+    ) -> Result<SpawnIndexerResult, String> {
+        self.cancellable_spawn(&id.clone(), |cancel| async move {
+            let spawned = self
+                .spawn_indexer_with_id(id, config, false, &cancel)
+                .await?;
+
+            let started = match self.start_indexer_cancellable(&spawned.id, &cancel).await {
+                Ok(result) => match wait {
+                    Some(timeout) => tokio::select! {
+                        waited = self.wait_for_running(&spawned.id, timeout) => {
+                            waited.map(|_| result)
+                        }
+                        _ = cancel.cancelled() => {
+                            Err(envio_utils::EnvioError::Cancelled.to_string())
+                        }
+                    },
+                    None => Ok(result),
+                },
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = &started {
+                warn!("Rolling back indexer {}: {}", spawned.id, e);
+                self.rollback_indexer(&spawned.id).await;
+            }
+            started
         })
+        .await
+    }
+
+    /// Removes `Stopped` and `Failed` indexers last checked more than `older_than` ago
+    ///
+    /// Failed indexers are stopped first, as their process or containers may still be around,
+    /// and kept if stopping them fails. Project directories under the base directory are deleted
+    /// with them, imported projects are left in place. Returns the pruned ids, `Running` and
+    /// `Starting` indexers are never pruned.
+    pub async fn prune_stopped(
+        &self,
+        older_than: std::time::Duration,
+    ) -> Result<Vec<String>, String> {
+        let stale: Vec<(String, bool)> = self
+            .indexers
+            .read()
+            .await
+            .values()
+            .filter(|process| {
+                matches!(
+                    process.status,
+                    IndexerStatus::Stopped | IndexerStatus::Failed(_)
+                ) && process.last_checked.elapsed() >= older_than
+            })
+            .map(|process| {
+                let failed = matches!(process.status, IndexerStatus::Failed(_));
+                (process.id.clone(), failed)
+            })
+            .collect();
+
+        let mut pruned = Vec::new();
+        for (id, failed) in stale {
+            if failed {
+                if let Err(e) = self.stop_indexer(&id).await {
+                    warn!("Not pruning indexer {}, stopping it failed: {}", id, e);
+                    continue;
+                }
+            }
+
+            let mut indexers = self.indexers.write().await;
+            // It may have been restarted or removed while unlocked
+            let Some(process) = indexers.get(&id) else {
+                continue;
+            };
+            if !matches!(
+                process.status,
+                IndexerStatus::Stopped | IndexerStatus::Failed(_)
+            ) {
+                continue;
+            }
+            let output_dir = &process.output_dir;
+            if output_dir.starts_with(self.envio_manager.base_dir()) && output_dir.exists() {
+                std::fs::remove_dir_all(output_dir)
+                    .map_err(|e| format!("Failed to remove project directory for {}: {}", id, e))?;
+            }
+            indexers.remove(&id);
+            info!("Pruned indexer {}", id);
+            pruned.push(id);
+        }
+        Ok(pruned)
+    }
+
+    async fn rollback_indexer(&self, id: &str) {
+        let _ = self.stop_indexer(id).await;
+        if let Some(process) = self.indexers.write().await.remove(id) {
+            if let Err(e) = std::fs::remove_dir_all(&process.output_dir) {
+                warn!("Failed to remove project directory for {}: {}", id, e);
+            }
+        }
     }
 
     pub async fn start_indexer(&self, id: &str) -> Result<SpawnIndexerResult, String> {
+        self.start_indexer_cancellable(id, &CancellationToken::new())
+            .await
+    }
+
+    /// Like [`Self::start_indexer`], but gives up before starting the indexer once `cancel` fires
+    ///
+    /// A cancelled codegen is killed.
+    async fn start_indexer_cancellable(
+        &self,
+        id: &str,
+        cancel: &CancellationToken,
+    ) -> Result<SpawnIndexerResult, String> {
         let mut indexers = self.indexers.write().await;
         let process = indexers
             .get_mut(id)
             .ok_or_else(|| format!("Indexer {} not found", id))?;
 
-        println!("Starting indexer {}", id);
-        process.status = IndexerStatus::Starting;
+        info!("Starting indexer {}", id);
+        self.set_status(process, IndexerStatus::Starting);
+        // The new process announces its own endpoint
+        process.graphql_endpoint = None;
 
-        // Run codegen
-        self.envio_manager
-            .run_codegen(&EnvioProject {
+        if self.deployment_mode == DeploymentMode::Docker {
+            // Codegen runs inside the container as part of its command
+            let project = EnvioProject {
                 id: id.to_string(),
                 dir: process.output_dir.clone(),
                 process: None,
-            })
-            .await?;
+            };
+            if cancel.is_cancelled() {
+                let e = envio_utils::EnvioError::Cancelled;
+                self.set_status(process, IndexerStatus::Failed(e.to_string()));
+                return Err(format!("Failed to start indexer: {}", e));
+            }
+            self.envio_manager
+                .report_progress(id, SpawnProgress::Starting);
+            let port = match self.docker()?.start_indexer(&project).await {
+                Ok(port) => port,
+                Err(e) => {
+                    self.set_status(process, IndexerStatus::Failed(e.to_string()));
+                    return Err(format!("Failed to start indexer: {}", e));
+                }
+            };
+            process.health_check.port = port;
+
+            process.last_checked = std::time::Instant::now();
+            process.push_log(format!(
+                "[{}] Indexer container started",
+                chrono::Local::now()
+            ));
+            self.attach_log_broadcast(process);
+
+            // The container announces its internal port, the host port is the one to use
+            let (graphql_endpoint, hasura_console) = endpoints_on(port);
+            process.graphql_endpoint = graphql_endpoint.clone();
+            return Ok(SpawnIndexerResult {
+                id: id.to_string(),
+                message: "Indexer started successfully".to_string(),
+                warnings: process.config.validation_warnings(),
+                graphql_endpoint,
+                hasura_console,
+            });
+        }
+
+        // Run codegen, unless the project is unchanged since the last start
+        let codegen_project = EnvioProject {
+            id: id.to_string(),
+            dir: process.output_dir.clone(),
+            process: None,
+        };
+        let codegen = tokio::select! {
+            result = self
+                .envio_manager
+                .run_codegen_if_stale(&codegen_project, self.force_codegen) => result,
+            _ = cancel.cancelled() => Err(envio_utils::EnvioError::Cancelled),
+        };
+        let codegen = codegen.and_then(|_| {
+            if cancel.is_cancelled() {
+                Err(envio_utils::EnvioError::Cancelled)
+            } else {
+                Ok(())
+            }
+        });
+        // No process is attached yet, so leaving it `Starting` would never be corrected
+        if let Err(e) = codegen {
+            self.set_status(process, IndexerStatus::Failed(e.to_string()));
+            return Err(format!("Failed to start indexer: {}", e));
+        }
 
         // Start dev mode
         let mut project = EnvioProject {
@@ -133,22 +911,25 @@ impl ServiceContext {
         // Start the indexer
         let start_result = self.envio_manager.start_dev(&mut project).await;
         if let Err(e) = start_result {
-            process.status = IndexerStatus::Failed(e.to_string());
+            self.set_status(process, IndexerStatus::Failed(e.to_string()));
             return Err(format!("Failed to start indexer: {}", e));
         }
 
         process.process = project.process;
         process.last_checked = std::time::Instant::now();
-        process
-            .logs
-            .push(format!("[{}] Indexer started", chrono::Local::now()));
+        process.push_log(format!("[{}] Indexer started", chrono::Local::now()));
+        self.attach_log_broadcast(process);
 
         // Update status to starting - we'll check health separately
-        process.status = IndexerStatus::Starting;
+        self.set_status(process, IndexerStatus::Starting);
 
+        let (graphql_endpoint, hasura_console) = self.endpoints();
         Ok(SpawnIndexerResult {
             id: id.to_string(),
             message: "Indexer started successfully".to_string(),
+            warnings: process.config.validation_warnings(),
+            graphql_endpoint,
+            hasura_console,
         })
     }
 
@@ -158,28 +939,109 @@ impl ServiceContext {
             .get_mut(id)
             .ok_or_else(|| format!("Indexer {} not found", id))?;
 
-        let mut project = EnvioProject {
-            id: id.to_string(),
-            dir: process.output_dir.clone(),
-            process: process.process.take(),
-        };
+        self.terminate(id, process).await?;
+
+        process.push_log(format!("[{}] Indexer stopped", chrono::Local::now()));
+        self.set_status(process, IndexerStatus::Stopped);
+        // Dropping the fan-out lets tails see the end of the stream once it drains
+        process.log_broadcast = None;
+
+        Ok(())
+    }
+
+    /// Stops the process or containers of an indexer without changing its status
+    ///
+    /// Fails if the indexer is still running afterwards, errors cleaning up after it are only
+    /// logged.
+    async fn terminate(&self, id: &str, process: &mut IndexerProcess) -> Result<(), String> {
+        let stop_result = match self.deployment_mode {
+            DeploymentMode::Local => {
+                let pid = process
+                    .process
+                    .as_mut()
+                    .and_then(envio_utils::project::running_pid)
+                    .or(process.attached_pid);
+                let attached = match process.attached_pid {
+                    Some(pid) => self.envio_manager.terminate_pid(pid).await,
+                    None => Ok(()),
+                };
+                let mut project = EnvioProject {
+                    id: id.to_string(),
+                    dir: process.output_dir.clone(),
+                    process: process.process.take(),
+                };
+                let stopped = self.envio_manager.stop_dev(&mut project).await;
+                // A process that survived stays tracked, so stopping can be retried
+                process.process = project.process;
+                let stop_result = attached.and(stopped);
+
+                if let Some(pid) = pid {
+                    if envio_utils::project::process_alive(pid).await {
+                        let reason = match &stop_result {
+                            Err(e) => format!(": {}", e),
+                            Ok(()) => String::new(),
+                        };
+                        process.push_log(format!(
+                            "[{}] Indexer process {} is still running after stopping it{}",
+                            chrono::Local::now(),
+                            pid,
+                            reason
+                        ));
+                        return Err(format!(
+                            "Indexer {} is still running as process {}{}",
+                            id, pid, reason
+                        ));
+                    }
+                }
+                process.attached_pid = None;
 
-        let stop_result = self.envio_manager.stop_dev(&mut project).await;
+                if pid.is_some() {
+                    let port = process.health_check.port;
+                    // Connecting to localhost is answered right away, it doesn't block for long
+                    let port_open = std::net::TcpStream::connect_timeout(
+                        &std::net::SocketAddr::from(([127, 0, 0, 1], port)),
+                        std::time::Duration::from_millis(500),
+                    )
+                    .is_ok();
+                    if port_open {
+                        // The process is gone, so whatever serves the port is not this indexer
+                        process.push_log(format!(
+                            "[{}] Port {} is still open after the indexer exited, another \
+                             process is serving it",
+                            chrono::Local::now(),
+                            port
+                        ));
+                    }
+                }
+                stop_result
+            }
+            DeploymentMode::Docker => {
+                let stop_result = self.docker()?.stop_indexer(id).await;
+                if let Err(e) = &stop_result {
+                    if let Ok(IndexerStatus::Running | IndexerStatus::Starting) =
+                        self.docker()?.indexer_status(id).await
+                    {
+                        process.push_log(format!(
+                            "[{}] Error stopping indexer: {}",
+                            chrono::Local::now(),
+                            e
+                        ));
+                        return Err(format!("Indexer {} is still running: {}", id, e));
+                    }
+                }
+                stop_result
+            }
+        };
 
         if let Err(e) = stop_result {
-            process.logs.push(format!(
+            // Termination was confirmed, the error only concerns the cleanup
+            process.push_log(format!(
                 "[{}] Error stopping indexer: {}",
                 chrono::Local::now(),
                 e
             ));
-            // Still mark as stopped even if we had an error
         }
 
-        process
-            .logs
-            .push(format!("[{}] Indexer stopped", chrono::Local::now()));
-        process.status = IndexerStatus::Stopped;
-
         Ok(())
     }
 
@@ -192,45 +1054,91 @@ impl ServiceContext {
         // Check status based on stored status enum variants
         match process.status {
             IndexerStatus::Starting | IndexerStatus::Running => {
-                // Only check status every few seconds to avoid too much overhead
+                // Only check status every few seconds to avoid too much overhead,
+                // backing off while the indexer stays unhealthy
                 let elapsed = process.last_checked.elapsed();
-                if elapsed > std::time::Duration::from_secs(5) {
-                    // Create a temporary EnvioProject with the current process
-                    let mut project = EnvioProject {
-                        id: id.to_string(),
-                        dir: process.output_dir.clone(),
-                        process: None,
-                    };
-
-                    // Move the process out temporarily to avoid clone issues
-                    if let Some(child_process) = process.process.take() {
-                        project.process = Some(child_process);
+                if elapsed > process.next_health_check_interval() {
+                    let monitor_result = match self.deployment_mode {
+                        DeploymentMode::Local => {
+                            // Move the process out temporarily to avoid clone issues
+                            match process.process.take() {
+                                Some(child_process) => {
+                                    let mut project = EnvioProject {
+                                        id: id.to_string(),
+                                        dir: process.output_dir.clone(),
+                                        process: Some(child_process),
+                                    };
 
-                        // Monitor using EnvioManager
-                        match self.envio_manager.monitor_indexer(&project).await {
-                            Ok(new_status) => {
-                                // Update status
-                                process.status = new_status;
+                                    // Monitor using EnvioManager
+                                    let result = self
+                                        .envio_manager
+                                        .monitor_indexer(&mut project, &process.health_check)
+                                        .await;
 
-                                // Add log entry
-                                let status_str: String = From::from(process.status.clone());
-                                process.logs.push(format!(
-                                    "[{}] Status updated: {}",
-                                    chrono::Local::now(),
-                                    status_str
-                                ));
+                                    // Move the process back
+                                    process.process = project.process;
+                                    Some(result)
+                                }
+                                None => match process.attached_pid {
+                                    Some(pid) => Some(
+                                        self.envio_manager
+                                            .monitor_pid(pid, &process.health_check)
+                                            .await
+                                            .map(|status| match status {
+                                                // Not our child, so its exit status is unknown
+                                                IndexerStatus::Stopped => IndexerStatus::Failed(
+                                                    format!("Indexer process {} exited", pid),
+                                                ),
+                                                status => status,
+                                            }),
+                                    ),
+                                    None => None,
+                                },
                             }
-                            Err(e) => {
-                                process.logs.push(format!(
-                                    "[{}] Error monitoring indexer: {}",
+                        }
+                        DeploymentMode::Docker => Some(self.docker()?.indexer_status(id).await),
+                    };
+
+                    match monitor_result {
+                        Some(Ok(new_status)) => {
+                            let alive = matches!(
+                                new_status,
+                                IndexerStatus::Running | IndexerStatus::Starting
+                            );
+                            // Update status
+                            let old_status = process.status.clone();
+                            process.record_health_check(new_status, self.max_health_failures);
+                            self.notify_transition(id, old_status, process.status.clone());
+
+                            // Failing an unhealthy indexer must not leave it running unmanaged
+                            if alive && matches!(process.status, IndexerStatus::Failed(_)) {
+                                let outcome = match self.terminate(id, process).await {
+                                    Ok(()) => "stopped it".to_string(),
+                                    Err(e) => format!("it is still running: {}", e),
+                                };
+                                process.push_log(format!(
+                                    "[{}] Indexer failed its health checks, {}",
                                     chrono::Local::now(),
-                                    e
+                                    outcome
                                 ));
                             }
-                        }
 
-                        // Move the process back
-                        process.process = project.process;
+                            // Add log entry
+                            let status_str: String = From::from(process.status.clone());
+                            process.push_log(format!(
+                                "[{}] Status updated: {}",
+                                chrono::Local::now(),
+                                status_str
+                            ));
+                        }
+                        Some(Err(e)) => {
+                            process.push_log(format!(
+                                "[{}] Error monitoring indexer: {}",
+                                chrono::Local::now(),
+                                e
+                            ));
+                        }
+                        None => {}
                     }
 
                     process.last_checked = std::time::Instant::now();
@@ -243,28 +1151,414 @@ impl ServiceContext {
         Ok(process.status.clone())
     }
 
-    pub async fn list_indexers(&self) -> Vec<String> {
-        let indexers = self.indexers.read().await;
-        indexers.keys().cloned().collect()
-    }
+    /// Adopts a local indexer process that outlived the context that started it, e.g. after a
+    /// restart of the blueprint, so it can be monitored and stopped again
+    ///
+    /// The process must be alive and run in the indexer's project directory.
+    pub async fn attach_existing_process(&self, id: &str, pid: u32) -> Result<(), String> {
+        if self.deployment_mode != DeploymentMode::Local {
+            return Err("Only local indexers run as processes that can be attached".to_string());
+        }
 
-    pub async fn get_indexer_status(&self, id: &str) -> Result<IndexerStatus, String> {
-        let indexers = self.indexers.read().await;
+        let mut indexers = self.indexers.write().await;
         let process = indexers
-            .get(id)
+            .get_mut(id)
             .ok_or_else(|| format!("Indexer {} not found", id))?;
-        Ok(process.status.clone())
+        if process
+            .process
+            .as_mut()
+            .and_then(envio_utils::project::running_pid)
+            .is_some()
+        {
+            return Err(format!("Indexer {} is already running", id));
+        }
+        if !envio_utils::project::process_alive(pid).await {
+            return Err(format!("Process {} is not running", pid));
+        }
+        if !envio_utils::project::process_runs_in(pid, &process.output_dir).await {
+            return Err(format!(
+                "Process {} does not run in the project directory {:?} of indexer {}",
+                pid, process.output_dir, id
+            ));
+        }
+
+        process.attached_pid = Some(pid);
+        // The next health check tells whether it is serving yet
+        self.set_status(process, IndexerStatus::Starting);
+        process.health_failures = 0;
+        process.push_log(format!(
+            "[{}] Attached to running process {}",
+            chrono::Local::now(),
+            pid
+        ));
+        Ok(())
     }
 
-    pub async fn get_indexer_config(&self, id: &str) -> Result<IndexerConfig, String> {
-        let indexers = self.indexers.read().await;
-        let process = indexers
+    /// Sets the endpoint used to check the health of a locally running indexer
+    pub async fn set_health_check(
+        &self,
+        id: &str,
+        health_check: HealthCheckConfig,
+    ) -> Result<(), String> {
+        let mut indexers = self.indexers.write().await;
+        let process = indexers
+            .get_mut(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        process.health_check = health_check;
+        Ok(())
+    }
+
+    /// Polls the indexer until it is `Running`.
+    ///
+    /// Returns early with an error if the indexer fails or stops, and times out after
+    /// `timeout`.
+    pub async fn wait_for_running(
+        &self,
+        id: &str,
+        timeout: std::time::Duration,
+    ) -> Result<(), String> {
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            match self.monitor_indexer(id).await? {
+                IndexerStatus::Running => return Ok(()),
+                IndexerStatus::Failed(reason) => {
+                    return Err(format!("Indexer {} failed: {}", id, reason))
+                }
+                IndexerStatus::Stopped => return Err(format!("Indexer {} is stopped", id)),
+                IndexerStatus::Configured | IndexerStatus::Starting => {}
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(format!(
+                    "Timed out after {:?} waiting for indexer {} to run",
+                    timeout, id
+                ));
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+
+    /// Returns the health of every indexer.
+    ///
+    /// Each indexer is checked through `monitor_indexer`, whose `last_checked` gate keeps
+    /// this cheap enough to call frequently.
+    pub async fn health_summary(&self) -> HealthSummary {
+        let mut summary = HealthSummary::default();
+
+        for id in self.list_indexers().await {
+            // The indexer may have been removed since listing
+            let Ok(status) = self.monitor_indexer(&id).await else {
+                continue;
+            };
+
+            summary.total += 1;
+            match status {
+                IndexerStatus::Configured => summary.configured += 1,
+                IndexerStatus::Starting => summary.starting += 1,
+                IndexerStatus::Running => summary.running += 1,
+                IndexerStatus::Failed(_) => summary.failed += 1,
+                IndexerStatus::Stopped => summary.stopped += 1,
+            }
+            if let Ok(Some(pid)) = self.get_indexer_pid(&id).await {
+                summary.pids.insert(id.clone(), pid);
+            }
+            summary.indexers.insert(id, status);
+        }
+
+        summary
+    }
+
+    /// Renders metrics for all indexers in the Prometheus text exposition format
+    pub async fn metrics_text(&self) -> String {
+        let indexers = self.indexers.read().await;
+        let mut ids: Vec<&String> = indexers.keys().collect();
+        ids.sort();
+
+        let mut out = String::new();
+        out.push_str("# HELP envio_indexers Number of indexers managed by this service\n");
+        out.push_str("# TYPE envio_indexers gauge\n");
+        out.push_str(&format!("envio_indexers {}\n", indexers.len()));
+
+        out.push_str(
+            "# HELP envio_indexer_status Current status of the indexer (1 for the active status)\n",
+        );
+        out.push_str("# TYPE envio_indexer_status gauge\n");
+        for id in &ids {
+            let status = match indexers[*id].status {
+                IndexerStatus::Configured => "configured",
+                IndexerStatus::Starting => "starting",
+                IndexerStatus::Running => "running",
+                IndexerStatus::Failed(_) => "failed",
+                IndexerStatus::Stopped => "stopped",
+            };
+            out.push_str(&format!(
+                "envio_indexer_status{{indexer_id=\"{}\",status=\"{}\"}} 1\n",
+                escape_label_value(id),
+                status
+            ));
+        }
+
+        let progress_metrics: [(&str, &str, fn(&IndexerProgress) -> Option<usize>); 3] = [
+            (
+                "envio_indexer_events_processed",
+                "Number of events processed by the indexer",
+                |p| p.events_processed,
+            ),
+            (
+                "envio_indexer_blocks_current",
+                "Latest block processed by the indexer",
+                |p| p.blocks_current,
+            ),
+            (
+                "envio_indexer_blocks_total",
+                "Target block height of the indexer",
+                |p| p.blocks_total,
+            ),
+        ];
+
+        for (name, help, value) in progress_metrics {
+            out.push_str(&format!("# HELP {} {}\n", name, help));
+            out.push_str(&format!("# TYPE {} gauge\n", name));
+            for id in &ids {
+                let mut chains: Vec<(&String, &IndexerProgress)> =
+                    indexers[*id].progress.iter().collect();
+                chains.sort_by(|a, b| a.0.cmp(b.0));
+                for (chain_id, progress) in chains {
+                    if let Some(value) = value(progress) {
+                        out.push_str(&format!(
+                            "{}{{indexer_id=\"{}\",chain_id=\"{}\"}} {}\n",
+                            name,
+                            escape_label_value(id),
+                            escape_label_value(chain_id),
+                            value
+                        ));
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
+    pub async fn list_indexers(&self) -> Vec<String> {
+        let indexers = self.indexers.read().await;
+        indexers.keys().cloned().collect()
+    }
+
+    /// Returns the ids of all indexers whose config name is `name`
+    pub async fn find_indexers_by_name(&self, name: &str) -> Vec<String> {
+        let indexers = self.indexers.read().await;
+        let mut ids: Vec<String> = indexers
+            .values()
+            .filter(|process| process.config.name == name)
+            .map(|process| process.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Returns the id of the single indexer named `name`
+    pub async fn get_by_name(&self, name: &str) -> Result<String, String> {
+        let mut ids = self.find_indexers_by_name(name).await;
+        match ids.len() {
+            0 => Err(format!("No indexer named {}", name)),
+            1 => Ok(ids.remove(0)),
+            n => Err(format!("{} indexers named {}: {}", n, name, ids.join(", "))),
+        }
+    }
+
+    pub async fn get_indexer_status(&self, id: &str) -> Result<IndexerStatus, String> {
+        let indexers = self.indexers.read().await;
+        let process = indexers
+            .get(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        Ok(process.status.clone())
+    }
+
+    /// Whether the indexer has a process or container that is up, whatever its recorded status
+    pub async fn has_live_instance(&self, id: &str) -> Result<bool, String> {
+        if self.deployment_mode == DeploymentMode::Docker {
+            self.get_indexer_status(id).await?;
+            // Without a container there is nothing to inspect
+            return Ok(matches!(
+                self.docker()?.indexer_status(id).await,
+                Ok(IndexerStatus::Starting | IndexerStatus::Running)
+            ));
+        }
+
+        let mut indexers = self.indexers.write().await;
+        let process = indexers
+            .get_mut(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        if let Some(child) = process.process.as_mut() {
+            return Ok(envio_utils::project::running_pid(child).is_some());
+        }
+        Ok(match process.attached_pid {
+            Some(pid) => envio_utils::project::process_alive(pid).await,
+            None => false,
+        })
+    }
+
+    /// OS process id of a locally running indexer, `None` if it is not running as a process
+    pub async fn get_indexer_pid(&self, id: &str) -> Result<Option<u32>, String> {
+        let mut indexers = self.indexers.write().await;
+        let process = indexers
+            .get_mut(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        let pid = process
+            .process
+            .as_mut()
+            .and_then(envio_utils::project::running_pid);
+        match (pid, process.attached_pid) {
+            (None, Some(pid)) if envio_utils::project::process_alive(pid).await => Ok(Some(pid)),
+            (pid, _) => Ok(pid),
+        }
+    }
+
+    /// GraphQL endpoint of an indexer, as announced in its logs if it did so yet
+    ///
+    /// The announcement is picked up by [`Self::subscribe_to_filtered_logs`], without it the
+    /// endpoint of [`Self::endpoints`] is assumed.
+    pub async fn graphql_endpoint(&self, id: &str) -> Result<Option<String>, String> {
+        let indexers = self.indexers.read().await;
+        let process = indexers
+            .get(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        Ok(process
+            .graphql_endpoint
+            .clone()
+            .or_else(|| self.endpoints().0))
+    }
+
+    pub async fn get_indexer_config(&self, id: &str) -> Result<IndexerConfig, String> {
+        let indexers = self.indexers.read().await;
+        let process = indexers
             .get(id)
             .ok_or_else(|| format!("Indexer {} not found", id))?;
         Ok(process.config.clone())
     }
 
+    /// Exports an indexer's config so it can be re-created elsewhere
+    pub async fn export_config(&self, id: &str, format: ConfigFormat) -> Result<String, String> {
+        self.get_indexer_config(id).await?.export(format)
+    }
+
+    /// Merge new contracts/deployments into an existing indexer's config.
+    ///
+    /// The envio project is re-initialized with the merged config. A running indexer is
+    /// stopped first and left in the `Configured` state, so it must be restarted with
+    /// `start_indexer` to pick up the changes.
+    pub async fn update_indexer_config(
+        &self,
+        id: &str,
+        new_config: IndexerConfig,
+    ) -> Result<IndexerConfig, String> {
+        let (mut merged, is_active) = {
+            let indexers = self.indexers.read().await;
+            let process = indexers
+                .get(id)
+                .ok_or_else(|| format!("Indexer {} not found", id))?;
+            let is_active = matches!(
+                process.status,
+                IndexerStatus::Starting | IndexerStatus::Running
+            ) || process.process.is_some();
+            (process.config.clone(), is_active)
+        };
+
+        merged.merge(new_config);
+        merged.normalize_addresses()?;
+        merged.dedup_deployments()?;
+        merged.validate()?;
+
+        if is_active {
+            self.stop_indexer(id).await?;
+        }
+
+        let mut indexers = self.indexers.write().await;
+        let process = indexers
+            .get_mut(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+
+        let project = self
+            .envio_manager
+            .init_project_cancellable(
+                id,
+                merged.contracts.clone(),
+                merged.ecosystem,
+                false,
+                &CancellationToken::new(),
+            )
+            .await
+            .map_err(|e| e.to_string())?
+            .project;
+
+        process.config = merged.clone();
+        process.output_dir = project.dir;
+        self.set_status(process, IndexerStatus::Configured);
+        process.push_log(format!(
+            "[{}] Indexer config updated, restart required",
+            chrono::Local::now()
+        ));
+
+        Ok(merged)
+    }
+
     // Getter methods for internal components
+    /// Validates a config and generates the envio config and schema a spawn would create,
+    /// without running envio or registering an indexer
+    ///
+    /// ABIs are fetched like in a spawn. Inferred ABIs are only resolved by envio itself, so
+    /// their contracts are listed without events.
+    pub async fn dry_run(&self, mut config: IndexerConfig) -> Result<DryRunReport, String> {
+        config.normalize_addresses()?;
+        config.dedup_deployments()?;
+        config.validate()?;
+        let mut warnings = config.validation_warnings();
+
+        let mut abis = HashMap::new();
+        let mut failures = Vec::new();
+        let mut skipped = Vec::new();
+        for contract in &config.contracts {
+            if contract.source.is_inferred() {
+                warnings.push(format!(
+                    "Contract {} uses an inferred ABI, its events are only known once envio runs",
+                    contract.name
+                ));
+                continue;
+            }
+            match self.envio_manager.get_abi(contract).await {
+                Ok(abi) => {
+                    abis.insert(contract.name.clone(), abi);
+                }
+                Err(e) if config.skip_failed_contracts => {
+                    warnings.push(format!(
+                        "Contract {} would be skipped because its ABI could not be fetched: {}",
+                        contract.name, e
+                    ));
+                    skipped.push(contract.name.clone());
+                }
+                Err(e) => failures.push(format!("{}: {}", contract.name, e)),
+            }
+        }
+        if !failures.is_empty() {
+            return Err(envio_utils::EnvioError::AbiUnavailable(failures).into());
+        }
+        config
+            .contracts
+            .retain(|contract| !skipped.contains(&contract.name));
+
+        let dir = tempfile::TempDir::new()
+            .map_err(|e| format!("Failed to create dry run directory: {}", e))?;
+        let (config_yaml, schema) =
+            envio_utils::generate_project_files(dir.path(), &config, &abis)?;
+        Ok(DryRunReport {
+            config_yaml,
+            schema,
+            warnings,
+        })
+    }
+
     pub fn get_envio_manager(&self) -> &Arc<EnvioManager> {
         &self.envio_manager
     }
@@ -279,8 +1573,11 @@ impl ServiceContext {
         let test_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
         std::fs::create_dir_all(&test_dir).expect("Failed to create test directory");
 
-        println!("Created test directory at: {:?}", test_dir);
-        Self::new(config, test_dir)
+        debug!("Created test directory at: {:?}", test_dir);
+        Self::builder(config)
+            .base_dir(test_dir)
+            .build()
+            .expect("Failed to create test context")
     }
 
     /// Subscribe to logs from a specific indexer
@@ -288,28 +1585,163 @@ impl ServiceContext {
         &self,
         id: &str,
     ) -> Result<mpsc::Receiver<IndexerLogMessage>, String> {
+        let (recent, mut live) = self.tail_logs(id, LOG_TAIL_CAPACITY).await?;
+        let (tx, rx) = mpsc::channel::<IndexerLogMessage>(100);
+
+        tokio::spawn(async move {
+            for msg in recent {
+                if tx.send(msg).await.is_err() {
+                    return;
+                }
+            }
+            loop {
+                match live.recv().await {
+                    Ok(msg) => {
+                        if tx.send(msg).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Log subscriber fell behind, skipped {} messages", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Returns up to `last_n` recent log messages and a receiver for all later ones.
+    ///
+    /// Unlike `subscribe_to_indexer_logs`, any number of subscribers can tail the same indexer;
+    /// they share one reader of the underlying stream. Once the stream has ended, the tail comes
+    /// from the persisted log file, or the buffered messages without one, and the receiver is
+    /// closed. The stream is only reopened for a process whose output hasn't been read yet.
+    pub async fn tail_logs(
+        &self,
+        id: &str,
+        last_n: usize,
+    ) -> Result<
+        (
+            Vec<IndexerLogMessage>,
+            broadcast::Receiver<IndexerLogMessage>,
+        ),
+        String,
+    > {
         let mut indexers = self.indexers.write().await;
         let process = indexers
             .get_mut(id)
             .ok_or_else(|| format!("Indexer {} not found", id))?;
 
-        // Create a temporary EnvioProject from the IndexerProcess
-        let mut project = EnvioProject {
-            id: process.id.clone(),
-            dir: process.output_dir.clone(),
-            process: process.process.take(),
+        if let Some(log_broadcast) = process.log_broadcast.as_ref().filter(|b| b.is_active()) {
+            return Ok(log_broadcast.subscribe(last_n));
+        }
+
+        // Normally attached when the indexer starts, but an imported or seeded one may lack it
+        let has_unread_output = match self.deployment_mode {
+            DeploymentMode::Local => process
+                .process
+                .as_ref()
+                .is_some_and(|child| child.stdout.is_some() || child.stderr.is_some()),
+            DeploymentMode::Docker => process.log_broadcast.is_none(),
         };
+        if has_unread_output {
+            let logs_rx = self.open_log_stream(process)?;
+            let log_broadcast = LogBroadcast::spawn(logs_rx, LOG_TAIL_CAPACITY);
+            let tail = log_broadcast.subscribe(last_n);
+            process.log_broadcast = Some(log_broadcast);
+            return Ok(tail);
+        }
 
-        // Subscribe to logs
-        let logs_rx = self
-            .envio_manager
-            .subscribe_to_logs(&mut project)
-            .map_err(|e| format!("Failed to subscribe to logs: {}", e))?;
+        let buffered = process
+            .log_broadcast
+            .as_ref()
+            .map(|b| b.subscribe(last_n).0)
+            .unwrap_or_default();
+        let log_file = self
+            .log_rotation
+            .is_some()
+            .then(|| process.output_dir.join(INDEXER_LOG_FILE));
+        drop(indexers);
 
-        // Move the process back
-        process.process = project.process;
+        let persisted = match log_file {
+            Some(log_file) => read_log_tail(&log_file, last_n).await,
+            None => None,
+        };
+        // Nothing is sent anymore, so the receiver reports the end right away
+        let (_, ended) = broadcast::channel(1);
+        Ok((persisted.unwrap_or(buffered), ended))
+    }
+
+    /// Reads the output of a freshly started indexer once, so any number of subscribers can share it
+    fn attach_log_broadcast(&self, process: &mut IndexerProcess) {
+        match self.open_log_stream(process) {
+            Ok(logs_rx) => {
+                process.log_broadcast = Some(LogBroadcast::spawn(logs_rx, LOG_TAIL_CAPACITY))
+            }
+            Err(e) => process.push_log(format!(
+                "[{}] Failed to attach to indexer logs: {}",
+                chrono::Local::now(),
+                e
+            )),
+        }
+    }
+
+    /// Takes the indexer's raw log stream, persisting it if log rotation is configured
+    fn open_log_stream(
+        &self,
+        process: &mut IndexerProcess,
+    ) -> Result<mpsc::Receiver<IndexerLogMessage>, String> {
+        let logs_rx = match self.deployment_mode {
+            DeploymentMode::Local => {
+                // Create a temporary EnvioProject from the IndexerProcess
+                let mut project = EnvioProject {
+                    id: process.id.clone(),
+                    dir: process.output_dir.clone(),
+                    process: process.process.take(),
+                };
+
+                // Subscribe to logs
+                let logs_rx = self.envio_manager.subscribe_to_logs(&mut project);
+
+                // Move the process back
+                process.process = project.process;
+
+                logs_rx.map_err(|e| format!("Failed to subscribe to logs: {}", e))?
+            }
+            // Container logs are read through the Docker API rather than a child process
+            DeploymentMode::Docker => self.docker()?.subscribe_to_logs(&process.id),
+        };
 
-        Ok(logs_rx)
+        // Containers announce the port inside the container, not the published one
+        let logs_rx = match self.deployment_mode {
+            DeploymentMode::Local => {
+                watch_announcements(self.indexers.clone(), process.id.clone(), logs_rx)
+            }
+            DeploymentMode::Docker => logs_rx,
+        };
+
+        match &self.log_rotation {
+            Some(rotation) => {
+                let log_file = RotatingLogFile::open(
+                    process.output_dir.join(INDEXER_LOG_FILE),
+                    rotation.clone(),
+                )
+                .map_err(|e| format!("Failed to open log file: {}", e))?;
+                Ok(persist_logs(log_file, logs_rx))
+            }
+            None => Ok(logs_rx),
+        }
+    }
+
+    /// Path of the file an indexer's logs are persisted to
+    pub async fn log_file_path(&self, id: &str) -> Result<PathBuf, String> {
+        let indexers = self.indexers.read().await;
+        let process = indexers
+            .get(id)
+            .ok_or_else(|| format!("Indexer {} not found", id))?;
+        Ok(process.output_dir.join(INDEXER_LOG_FILE))
     }
 
     /// Subscribe to filtered logs from a specific indexer
@@ -324,16 +1756,17 @@ impl ServiceContext {
         // Create a new channel for the filtered logs
         let (tx, rx) = mpsc::channel::<String>(100);
 
+        let indexers = self.indexers.clone();
+        let id = id.to_string();
+
         // Spawn a task to filter the logs
         tokio::spawn(async move {
-            // Track previously seen lines to avoid duplicates
-            let mut seen_lines = std::collections::HashSet::new();
+            // Track recently seen lines to collapse duplicates
+            let mut dedup = LogDeduplicator::default();
             // Skip logo after seeing it once
             let mut shown_logo = false;
-            // Last progress information for summarizing
-            let mut last_progress: Option<IndexerProgress> = None;
-            // Timestamp of last progress update
-            let mut last_progress_time = std::time::Instant::now();
+            // Progress of each chain, summarized as updates come in
+            let mut progress_tracker = ProgressTracker::default();
 
             while let Some(msg) = raw_logs.recv().await {
                 match msg {
@@ -363,9 +1796,8 @@ impl ServiceContext {
                             || line.contains("GraphQL:")
                             || line.contains("Chain ID:");
 
-                        if always_show || !seen_lines.contains(&line) {
-                            let _ = tx.send(line.clone()).await;
-                            seen_lines.insert(line);
+                        if always_show || dedup.should_emit(&line, std::time::Instant::now()) {
+                            let _ = tx.send(line).await;
                         }
                     }
                     IndexerLogMessage::Stderr(line) => {
@@ -373,50 +1805,18 @@ impl ServiceContext {
                         let _ = tx.send(format!("ERROR: {}", line)).await;
                     }
                     IndexerLogMessage::Progress(progress) => {
-                        let events_processed = progress.clone().events_processed;
-                        let blocks_current = progress.clone().blocks_current;
-                        let blocks_total = progress.clone().blocks_total;
-                        let chain_id = progress.clone().chain_id;
-                        let percentage = progress.clone().percentage;
-                        let eta = progress.clone().eta;
-
-                        // Only send progress updates periodically or on significant changes
-                        let now = std::time::Instant::now();
-                        let time_to_update = now.duration_since(last_progress_time)
-                            > std::time::Duration::from_secs(5);
-
-                        let significant_change = if let Some(last) = &last_progress {
-                            events_processed != last.events_processed
-                                || chain_id != last.chain_id
-                                || eta != last.eta
-                                || (percentage.is_some() && last.percentage.is_some() && {
-                                    // Calculate absolute difference without using .abs()
-                                    let curr = percentage.unwrap_or(0);
-                                    let prev = last.percentage.unwrap_or(0);
-                                    let diff = if curr > prev {
-                                        curr - prev
-                                    } else {
-                                        prev - curr
-                                    };
-                                    diff >= 5
-                                })
-                        } else {
-                            true
-                        };
+                        // Record the latest progress for metrics
+                        if let Some(process) = indexers.write().await.get_mut(&id) {
+                            let chain_key = progress
+                                .chain_id
+                                .clone()
+                                .unwrap_or_else(|| "unknown".to_string());
+                            process.progress.insert(chain_key, progress.clone());
+                        }
 
-                        if time_to_update || significant_change {
-                            let progress_msg = format!(
-                                "PROGRESS: Events: {}, Blocks: {}/{}, Chain: {}, {}%, ETA: {}",
-                                events_processed.unwrap_or(0),
-                                blocks_current.unwrap_or(0),
-                                blocks_total.unwrap_or(0),
-                                chain_id.unwrap_or_else(|| "unknown".to_string()),
-                                percentage.unwrap_or(0),
-                                eta.unwrap_or_else(|| "unknown".to_string())
-                            );
-                            let _ = tx.send(progress_msg).await;
-                            last_progress = Some(progress);
-                            last_progress_time = now;
+                        for summary in progress_tracker.record(progress, std::time::Instant::now())
+                        {
+                            let _ = tx.send(summary).await;
                         }
                     }
                 }
@@ -426,3 +1826,471 @@ impl ServiceContext {
         Ok(rx)
     }
 }
+
+/// Builder for [`ServiceContext`]
+///
+/// Without a deployment mode the context runs indexers locally.
+pub struct ServiceContextBuilder {
+    config: GadgetConfiguration,
+    base_dir: Option<PathBuf>,
+    deployment_mode: DeploymentMode,
+    docker_config: Option<EnvioDockerConfig>,
+    envio_manager: Option<EnvioManager>,
+    log_rotation: Option<LogRotationConfig>,
+    max_health_failures: u32,
+    force_codegen: bool,
+    check_writable: bool,
+    ipfs_gateway: Option<String>,
+}
+
+impl ServiceContextBuilder {
+    pub fn new(config: GadgetConfiguration) -> Self {
+        Self {
+            config,
+            base_dir: None,
+            deployment_mode: DeploymentMode::Local,
+            docker_config: None,
+            envio_manager: None,
+            log_rotation: Some(LogRotationConfig::default()),
+            max_health_failures: DEFAULT_MAX_HEALTH_FAILURES,
+            force_codegen: false,
+            check_writable: true,
+            ipfs_gateway: None,
+        }
+    }
+
+    /// Directory holding indexer projects, not needed when an [`EnvioManager`] is given
+    pub fn base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    pub fn deployment_mode(mut self, deployment_mode: DeploymentMode) -> Self {
+        self.deployment_mode = deployment_mode;
+        self
+    }
+
+    /// Runs indexers in Docker containers using `docker_config`
+    pub fn docker(mut self, docker_config: EnvioDockerConfig) -> Self {
+        self.deployment_mode = DeploymentMode::Docker;
+        self.docker_config = Some(docker_config);
+        self
+    }
+
+    /// Uses a preconfigured manager instead of one created for the base directory
+    pub fn envio_manager(mut self, envio_manager: EnvioManager) -> Self {
+        self.envio_manager = Some(envio_manager);
+        self
+    }
+
+    /// Rotation settings for persisted indexer logs, `None` disables persistence
+    pub fn log_rotation(mut self, log_rotation: Option<LogRotationConfig>) -> Self {
+        self.log_rotation = log_rotation;
+        self
+    }
+
+    pub fn max_health_failures(mut self, max_health_failures: u32) -> Self {
+        self.max_health_failures = max_health_failures;
+        self
+    }
+
+    /// Runs `envio codegen` on every start instead of only when the project changed
+    pub fn force_codegen(mut self, force_codegen: bool) -> Self {
+        self.force_codegen = force_codegen;
+        self
+    }
+
+    /// Gateway IPFS-hosted ABIs are fetched through, also for a preconfigured manager
+    pub fn ipfs_gateway(mut self, ipfs_gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = Some(ipfs_gateway.into());
+        self
+    }
+
+    /// Whether building checks that the base directory is writable, see
+    /// [`ServiceContext::check_writable`]. On by default
+    pub fn check_writable(mut self, check_writable: bool) -> Self {
+        self.check_writable = check_writable;
+        self
+    }
+
+    /// Builds the context, failing if no usable base directory is configured
+    ///
+    /// The base directory must be absolute. Docker mode without a Docker config uses
+    /// [`EnvioDockerConfig::default`].
+    pub fn build(self) -> Result<ServiceContext, String> {
+        let envio_manager = match self.envio_manager {
+            Some(envio_manager) => envio_manager,
+            None => {
+                let base_dir = self
+                    .base_dir
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .ok_or_else(|| {
+                        "Indexer base directory is empty, configure a data_dir".to_string()
+                    })?;
+                // What a relative path points to depends on the cwd at the time it's used
+                if !base_dir.is_absolute() {
+                    return Err(format!(
+                        "Indexer base directory {:?} is relative, configure an absolute data_dir",
+                        base_dir
+                    ));
+                }
+                EnvioManager::new(base_dir)
+            }
+        };
+        let envio_manager = match self.ipfs_gateway {
+            Some(ipfs_gateway) => envio_manager.with_ipfs_gateway(ipfs_gateway),
+            None => envio_manager,
+        };
+
+        let envio_docker = match self.deployment_mode {
+            DeploymentMode::Docker => Some(Arc::new(EnvioDocker::new(
+                self.docker_config.unwrap_or_default(),
+            )?)),
+            DeploymentMode::Local => None,
+        };
+
+        let context = ServiceContext {
+            config: self.config,
+            call_id: None,
+            indexers: Arc::new(RwLock::new(HashMap::new())),
+            envio_manager: Arc::new(envio_manager),
+            envio_docker,
+            deployment_mode: self.deployment_mode,
+            log_rotation: self.log_rotation,
+            max_health_failures: self.max_health_failures,
+            force_codegen: self.force_codegen,
+            pending_spawns: Arc::new(RwLock::new(HashMap::new())),
+            events_tx: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+        };
+        if self.check_writable {
+            context.check_writable()?;
+        }
+        Ok(context)
+    }
+}
+
+/// Fans one indexer's log stream out to any number of subscribers.
+///
+/// The most recent `capacity` messages are kept so that new subscribers can catch up.
+#[derive(Clone)]
+pub struct LogBroadcast {
+    tx: broadcast::Sender<IndexerLogMessage>,
+    recent: Arc<std::sync::Mutex<std::collections::VecDeque<IndexerLogMessage>>>,
+    capacity: usize,
+    active: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl LogBroadcast {
+    /// Starts forwarding every message of `logs_rx` to subscribers
+    pub fn spawn(mut logs_rx: mpsc::Receiver<IndexerLogMessage>, capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        let log_broadcast = Self {
+            tx,
+            recent: Arc::new(std::sync::Mutex::new(std::collections::VecDeque::new())),
+            capacity,
+            active: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+        };
+
+        let forwarder = log_broadcast.clone();
+        tokio::spawn(async move {
+            while let Some(msg) = logs_rx.recv().await {
+                forwarder.publish(msg);
+            }
+            forwarder
+                .active
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+        });
+
+        log_broadcast
+    }
+
+    /// Returns false once the underlying stream has ended
+    pub fn is_active(&self) -> bool {
+        self.active.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Returns up to `last_n` buffered messages and a receiver for every later message
+    pub fn subscribe(
+        &self,
+        last_n: usize,
+    ) -> (
+        Vec<IndexerLogMessage>,
+        broadcast::Receiver<IndexerLogMessage>,
+    ) {
+        // Subscribing under the lock ensures no message is missed or seen twice
+        let recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        let rx = self.tx.subscribe();
+        let skip = recent.len().saturating_sub(last_n);
+        (recent.iter().skip(skip).cloned().collect(), rx)
+    }
+
+    fn publish(&self, msg: IndexerLogMessage) {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() >= self.capacity {
+            recent.pop_front();
+        }
+        recent.push_back(msg.clone());
+        // Having no subscribers is fine
+        let _ = self.tx.send(msg);
+    }
+}
+
+/// Latest and last summarized progress of one chain
+struct ChainProgress {
+    latest: IndexerProgress,
+    last_emitted: IndexerProgress,
+    last_emitted_at: std::time::Instant,
+}
+
+/// Tracks progress per chain so that a multi-chain indexer's chains don't overwrite each other.
+///
+/// A chain's summary is emitted on a significant change or every 5 seconds, followed by an
+/// aggregate over all chains once more than one is known.
+#[derive(Default)]
+pub struct ProgressTracker {
+    chains: HashMap<String, ChainProgress>,
+}
+
+impl ProgressTracker {
+    /// Latest progress of `chain_id`
+    pub fn get(&self, chain_id: &str) -> Option<&IndexerProgress> {
+        self.chains.get(chain_id).map(|chain| &chain.latest)
+    }
+
+    /// Events processed across all chains
+    pub fn total_events(&self) -> usize {
+        self.chains
+            .values()
+            .filter_map(|chain| chain.latest.events_processed)
+            .sum()
+    }
+
+    /// Percentage of the least synced chain
+    pub fn min_percentage(&self) -> Option<usize> {
+        self.chains
+            .values()
+            .filter_map(|chain| chain.latest.percentage)
+            .min()
+    }
+
+    /// Records `progress`, returning the summary lines to emit
+    pub fn record(&mut self, progress: IndexerProgress, now: std::time::Instant) -> Vec<String> {
+        let chain_id = progress
+            .chain_id
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let should_emit = match self.chains.get_mut(&chain_id) {
+            Some(chain) => {
+                chain.latest = progress.clone();
+                let last = &chain.last_emitted;
+                let time_to_update =
+                    now.duration_since(chain.last_emitted_at) > std::time::Duration::from_secs(5);
+                let significant_change = progress.events_processed != last.events_processed
+                    || progress.eta != last.eta
+                    || matches!(
+                        (progress.percentage, last.percentage),
+                        (Some(curr), Some(prev)) if curr.abs_diff(prev) >= 5
+                    );
+                time_to_update || significant_change
+            }
+            None => {
+                self.chains.insert(
+                    chain_id.clone(),
+                    ChainProgress {
+                        latest: progress.clone(),
+                        last_emitted: progress.clone(),
+                        last_emitted_at: now,
+                    },
+                );
+                true
+            }
+        };
+        if !should_emit {
+            return Vec::new();
+        }
+
+        if let Some(chain) = self.chains.get_mut(&chain_id) {
+            chain.last_emitted = progress.clone();
+            chain.last_emitted_at = now;
+        }
+
+        let mut summaries = vec![format!(
+            "PROGRESS: Events: {}, Blocks: {}/{}, Chain: {}, {}%, ETA: {}",
+            progress.events_processed.unwrap_or(0),
+            progress.blocks_current.unwrap_or(0),
+            progress.blocks_total.unwrap_or(0),
+            chain_id,
+            progress.percentage.unwrap_or(0),
+            progress.eta.unwrap_or_else(|| "unknown".to_string())
+        )];
+        if self.chains.len() > 1 {
+            summaries.push(format!(
+                "PROGRESS: All {} chains: Events: {}, {}%",
+                self.chains.len(),
+                self.total_events(),
+                self.min_percentage().unwrap_or(0)
+            ));
+        }
+        summaries
+    }
+}
+
+/// Collapses repeated log lines within a bounded window.
+///
+/// A line is suppressed if it was emitted less than `window` ago. At most `capacity` lines
+/// are remembered, so memory stays bounded and old lines eventually reappear.
+pub struct LogDeduplicator {
+    window: std::time::Duration,
+    capacity: usize,
+    last_seen: HashMap<String, std::time::Instant>,
+    order: std::collections::VecDeque<String>,
+}
+
+impl Default for LogDeduplicator {
+    fn default() -> Self {
+        Self::new(std::time::Duration::from_secs(60), 500)
+    }
+}
+
+impl LogDeduplicator {
+    pub fn new(window: std::time::Duration, capacity: usize) -> Self {
+        Self {
+            window,
+            capacity,
+            last_seen: HashMap::new(),
+            order: std::collections::VecDeque::new(),
+        }
+    }
+
+    /// Returns true if `line` should be emitted at `now`, recording it as seen
+    pub fn should_emit(&mut self, line: &str, now: std::time::Instant) -> bool {
+        if let Some(seen) = self.last_seen.get(line) {
+            if now.duration_since(*seen) < self.window {
+                return false;
+            }
+            self.order.retain(|l| l != line);
+        }
+
+        self.last_seen.insert(line.to_string(), now);
+        self.order.push_back(line.to_string());
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.last_seen.remove(&oldest);
+            }
+        }
+
+        true
+    }
+}
+
+/// GraphQL endpoint and Hasura console URLs of an indexer serving on `port`
+fn endpoints_on(port: u16) -> (Option<String>, Option<String>) {
+    (
+        Some(format!("http://localhost:{}/v1/graphql", port)),
+        Some(format!("http://localhost:{}/console", port)),
+    )
+}
+
+/// Reads the ABI at `abi_path` of an imported project, which must stay inside `project_dir`
+fn read_project_abi(project_dir: &Path, abi_path: &Path) -> Result<String, String> {
+    let full_path = project_dir
+        .join(abi_path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {:?}: {}", abi_path, e))?;
+    if !full_path.starts_with(project_dir) {
+        return Err(format!("{:?} is outside of the project", abi_path));
+    }
+    std::fs::read_to_string(&full_path)
+        .map_err(|e| format!("Failed to read {:?}: {}", full_path, e))
+}
+
+/// Reads the last `last_n` lines persisted to `log_file`, `None` if it can't be read
+async fn read_log_tail(log_file: &Path, last_n: usize) -> Option<Vec<IndexerLogMessage>> {
+    let contents = tokio::fs::read_to_string(log_file).await.ok()?;
+    let lines: Vec<&str> = contents.lines().collect();
+    let skip = lines.len().saturating_sub(last_n);
+    Some(
+        lines[skip..]
+            .iter()
+            .map(|line| match line.strip_prefix("ERROR: ") {
+                Some(line) => IndexerLogMessage::Stderr(line.to_string()),
+                None => IndexerLogMessage::Stdout(line.to_string()),
+            })
+            .collect(),
+    )
+}
+
+/// Records the GraphQL endpoint announced in the logs of indexer `id` before forwarding them
+///
+/// envio may serve on another port than the one assumed by `endpoints`, so the health check
+/// follows the announced port.
+fn watch_announcements(
+    indexers: Arc<RwLock<HashMap<String, IndexerProcess>>>,
+    id: String,
+    mut logs_rx: mpsc::Receiver<IndexerLogMessage>,
+) -> mpsc::Receiver<IndexerLogMessage> {
+    let (tx, rx) = mpsc::channel::<IndexerLogMessage>(100);
+
+    tokio::spawn(async move {
+        while let Some(msg) = logs_rx.recv().await {
+            if let IndexerLogMessage::Stdout(line) = &msg {
+                if let Some(endpoint) = envio_utils::project::parse_graphql_endpoint(line) {
+                    if let Some(process) = indexers.write().await.get_mut(&id) {
+                        if let Some(port) = reqwest::Url::parse(&endpoint)
+                            .ok()
+                            .and_then(|url| url.port_or_known_default())
+                        {
+                            process.health_check.port = port;
+                        }
+                        process.graphql_endpoint = Some(endpoint);
+                    }
+                }
+            }
+
+            // Keep watching even if the subscriber went away
+            let _ = tx.send(msg).await;
+        }
+    });
+
+    rx
+}
+
+/// Writes every message to `log_file` before forwarding it to the returned receiver
+///
+/// File writes block, so the messages are persisted on a thread of the blocking pool.
+fn persist_logs(
+    mut log_file: RotatingLogFile,
+    mut logs_rx: mpsc::Receiver<IndexerLogMessage>,
+) -> mpsc::Receiver<IndexerLogMessage> {
+    let (tx, rx) = mpsc::channel::<IndexerLogMessage>(100);
+
+    tokio::task::spawn_blocking(move || {
+        while let Some(msg) = logs_rx.blocking_recv() {
+            let line = match &msg {
+                IndexerLogMessage::Stdout(line) => Some(line.clone()),
+                IndexerLogMessage::Stderr(line) => Some(format!("ERROR: {}", line)),
+                // Progress is derived from stdout lines that are already persisted
+                IndexerLogMessage::Progress(_) => None,
+            };
+            if let Some(line) = line {
+                if let Err(e) = log_file.write_line(&line) {
+                    warn!("Failed to write to {:?}: {}", log_file.path(), e);
+                }
+            }
+
+            // Keep persisting even if the subscriber went away
+            let _ = tx.blocking_send(msg);
+        }
+    });
+
+    rx
+}
+
+/// Escapes a Prometheus label value
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,12 +1,12 @@
 use crate::envio_utils::config::{ContractConfig, ContractDeployment, ContractSource};
-use crate::envio_utils::IndexerConfig;
+use crate::envio_utils::{abi_file_path, IndexerConfig};
 use fake::faker::address::en::CountryCode;
 use fake::faker::company::en::CompanyName;
 use fake::faker::internet::en::DomainSuffix;
 use fake::{Fake, Faker};
 use rand::seq::SliceRandom;
 use rand::Rng;
-use tempfile::TempDir;
+use std::path::{Path, PathBuf};
 
 pub mod erc20_abi;
 pub mod greeter_abi;
@@ -54,20 +54,26 @@ pub fn generate_random_api_key() -> String {
 }
 
 // Contract generation utilities
-fn create_deployment(
+pub fn create_deployment(
     network_id: &str,
     address: Option<String>,
     rpc_url: Option<String>,
     proxy_address: Option<String>,
     start_block: Option<u64>,
 ) -> ContractDeployment {
-    ContractDeployment::new(
-        network_id.to_string(),
-        address.unwrap_or_else(generate_random_address),
-        rpc_url.unwrap_or_else(generate_random_rpc_url),
-        proxy_address,
-        start_block,
-    )
+    let mut builder = ContractDeployment::builder()
+        .network_id(network_id)
+        .address(address.unwrap_or_else(generate_random_address))
+        .rpc_url(rpc_url.unwrap_or_else(generate_random_rpc_url));
+    if let Some(proxy_address) = proxy_address {
+        builder = builder.proxy_address(proxy_address);
+    }
+    if let Some(start_block) = start_block {
+        builder = builder.start_block(start_block);
+    }
+    builder
+        .build()
+        .expect("Test deployment has all required fields")
 }
 
 pub fn create_test_contract(name: &str, network_id: &str) -> ContractConfig {
@@ -76,6 +82,7 @@ pub fn create_test_contract(name: &str, network_id: &str) -> ContractConfig {
         ContractSource::Abi {
             abi: Some(GREETER_ABI.to_string()),
             url: None,
+            path: None,
         },
         vec![create_deployment(
             network_id,
@@ -92,6 +99,7 @@ pub fn create_test_explorer_contract(name: &str, network_id: &str) -> ContractCo
         name.to_string(),
         ContractSource::Explorer {
             api_url: "test_key".to_string(),
+            api_keys: Default::default(),
         },
         vec![create_deployment(
             network_id,
@@ -103,6 +111,20 @@ pub fn create_test_explorer_contract(name: &str, network_id: &str) -> ContractCo
     )
 }
 
+pub fn create_test_inferred_contract(name: &str, network_id: &str) -> ContractConfig {
+    ContractConfig::new(
+        name.to_string(),
+        ContractSource::Inferred,
+        vec![create_deployment(
+            network_id,
+            Some("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string()),
+            None,
+            None,
+            None,
+        )],
+    )
+}
+
 pub fn generate_random_contract_config() -> ContractConfig {
     let mut rng = rand::thread_rng();
     let network_id = COMMON_NETWORK_IDS.choose(&mut rng).unwrap();
@@ -111,10 +133,12 @@ pub fn generate_random_contract_config() -> ContractConfig {
         ContractSource::Abi {
             abi: Some(GREETER_ABI.to_string()),
             url: None,
+            path: None,
         }
     } else {
         ContractSource::Explorer {
             api_url: generate_random_api_key(),
+            api_keys: Default::default(),
         }
     };
 
@@ -143,6 +167,7 @@ pub fn generate_multi_chain_contract() -> ContractConfig {
         ContractSource::Abi {
             abi: Some(GREETER_ABI.to_string()),
             url: None,
+            path: None,
         },
         COMMON_NETWORK_IDS
             .iter()
@@ -157,6 +182,7 @@ pub fn generate_multi_address_contract(network_id: &str, num_addresses: usize) -
         ContractSource::Abi {
             abi: Some(GREETER_ABI.to_string()),
             url: None,
+            path: None,
         },
         (0..num_addresses)
             .map(|_| create_deployment(network_id, None, None, None, None))
@@ -171,6 +197,7 @@ pub fn create_usdc_contract() -> IndexerConfig {
         ContractSource::Abi {
             abi: Some(ERC20_ABI.to_string()),
             url: None,
+            path: None,
         },
         vec![ContractDeployment::new(
             "1".to_string(), // Ethereum mainnet
@@ -184,38 +211,96 @@ pub fn create_usdc_contract() -> IndexerConfig {
     IndexerConfig::new("usdc_indexer_test".to_string(), vec![contract])
 }
 
-// File verification utilities
-pub fn verify_abi_file(temp_dir: &TempDir, indexer_name: &str, contract_name: &str) -> bool {
-    temp_dir
-        .path()
-        .join(format!("{}_{}_abi.json", indexer_name, contract_name))
-        .exists()
-}
-
-pub fn read_abi_file(
-    temp_dir: &TempDir,
-    indexer_name: &str,
-    contract_name: &str,
-) -> Option<String> {
-    std::fs::read_to_string(
-        temp_dir
-            .path()
-            .join(format!("{}_{}_abi.json", indexer_name, contract_name)),
+/// Chain id of a local Anvil node
+pub const ANVIL_CHAIN_ID: &str = "31337";
+
+/// RPC URL of a local Anvil node on its default port
+pub const ANVIL_RPC_URL: &str = "http://localhost:8545";
+
+/// Address of the first contract deployed by Anvil's default account
+pub const ANVIL_FIRST_CONTRACT_ADDRESS: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+/// Create a Greeter contract deployed at `address` on a local Anvil node
+pub fn create_anvil_contract(name: &str, address: &str) -> ContractConfig {
+    ContractConfig::new(
+        name.to_string(),
+        ContractSource::Abi {
+            abi: Some(GREETER_ABI.to_string()),
+            url: None,
+            path: None,
+        },
+        vec![ContractDeployment::new(
+            ANVIL_CHAIN_ID.to_string(),
+            address.to_string(),
+            ANVIL_RPC_URL.to_string(),
+            None,
+            Some(0), // A fresh Anvil chain starts at genesis
+        )],
     )
-    .ok()
-}
-
-pub fn count_abi_files(temp_dir: &TempDir) -> usize {
-    std::fs::read_dir(temp_dir.path())
-        .unwrap()
-        .filter(|entry| {
-            entry
-                .as_ref()
-                .unwrap()
-                .path()
-                .extension()
-                .unwrap_or_default()
-                == "json"
+}
+
+/// Create an indexer config that only needs a local Anvil node, no mainnet RPC access
+pub fn create_anvil_indexer() -> IndexerConfig {
+    IndexerConfig::new(
+        "anvil_indexer_test".to_string(),
+        vec![create_anvil_contract(
+            "Greeter",
+            ANVIL_FIRST_CONTRACT_ADDRESS,
+        )],
+    )
+}
+
+/// Writes an executable stand-in for the envio CLI into `dir` and returns its path
+///
+/// `init` writes a minimal `config.yaml` and waits at the final prompt, `codegen` creates
+/// `generated/` and appends a line to `codegen_runs`, and `dev` sleeps like a running indexer.
+pub fn write_stub_envio(dir: &Path) -> PathBuf {
+    write_stub_envio_with(
+        dir,
+        "mkdir -p generated; echo run >> codegen_runs",
+        "exec sleep 30",
+    )
+}
+
+/// Like [`write_stub_envio`], with the shell commands run for `codegen` and `dev`
+pub fn write_stub_envio_with(dir: &Path, codegen: &str, dev: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let stub = dir.join("envio");
+    std::fs::write(
+        &stub,
+        format!(
+            "#!/bin/sh\n\
+             case \"$1\" in\n\
+               init) echo 'name: stubbed' > config.yaml; echo '? Project template ready'; exec sleep 30 ;;\n\
+               codegen) {} ;;\n\
+               dev) {} ;;\n\
+             esac\n",
+            codegen, dev
+        ),
+    )
+    .expect("Failed to write stub envio");
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))
+        .expect("Failed to make stub envio executable");
+    stub
+}
+
+// File verification utilities
+pub fn verify_abi_file(project_dir: &Path, contract_name: &str) -> bool {
+    project_dir.join(abi_file_path(contract_name)).exists()
+}
+
+pub fn read_abi_file(project_dir: &Path, contract_name: &str) -> Option<String> {
+    std::fs::read_to_string(project_dir.join(abi_file_path(contract_name))).ok()
+}
+
+pub fn count_abi_files(project_dir: &Path) -> usize {
+    std::fs::read_dir(project_dir.join("abis"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().extension().unwrap_or_default() == "json")
+                .count()
         })
-        .count()
+        .unwrap_or(0)
 }
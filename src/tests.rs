@@ -1,8 +1,8 @@
 use super::*;
 use crate::{
     envio_utils::{project::IndexerStatus, IndexerConfig},
-    jobs::spawn_indexer_local,
-    service_context::{ServiceContext, SpawnIndexerParams, SpawnIndexerResult},
+    jobs::{spawn_indexer_local, stop_indexer_local},
+    service_context::{IndexerProcess, ServiceContext, SpawnIndexerParams, SpawnIndexerResult},
     test_utils::{create_test_contract, create_usdc_contract},
 };
 use blueprint_sdk::{config::GadgetConfiguration, tokio};
@@ -48,7 +48,8 @@ impl Drop for TestCleanup {
 #[tokio::test]
 async fn test_spawn_multi_contract_indexer_local() {
     // Setup test environment
-    let context = ServiceContext::new(GadgetConfiguration::default(), PathBuf::from("."));
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
     let mut cleanup = TestCleanup::new(context.clone());
 
     // Create test contracts configuration using test utils
@@ -58,7 +59,7 @@ async fn test_spawn_multi_contract_indexer_local() {
     ];
 
     let config = IndexerConfig::new("multi_network_test".to_string(), contracts);
-    let params = SpawnIndexerParams { config };
+    let params = SpawnIndexerParams::new(config);
     let params_bytes = serde_json::to_vec(&params).unwrap();
 
     // Test local indexer spawn
@@ -69,19 +70,33 @@ async fn test_spawn_multi_contract_indexer_local() {
     cleanup.set_indexer_id(result.id.clone());
 
     assert!(result.id.contains("multi_network_test"));
+    assert_eq!(
+        result.graphql_endpoint.as_deref(),
+        Some("http://localhost:8080/v1/graphql")
+    );
+}
+
+#[test]
+fn test_local_endpoints() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let (graphql, console) = context.endpoints();
+    assert_eq!(graphql.as_deref(), Some("http://localhost:8080/v1/graphql"));
+    assert_eq!(console.as_deref(), Some("http://localhost:8080/console"));
 }
 
 #[tokio::test]
 async fn test_spawn_usdc_indexer() {
     use crate::envio_utils::IndexerLogMessage;
 
-    let context = ServiceContext::new(GadgetConfiguration::default(), PathBuf::from("."));
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
     let mut cleanup = TestCleanup::new(context.clone());
 
     let config = test_utils::create_usdc_contract();
     println!("Running USDC indexer test...");
 
-    let params = SpawnIndexerParams { config };
+    let params = SpawnIndexerParams::new(config);
     let params_bytes = serde_json::to_vec(&params).unwrap();
 
     // Test local indexer spawn
@@ -149,3 +164,1623 @@ async fn test_indexer_config_validation() {
     let config = IndexerConfig::new("".to_string(), vec![create_test_contract("Test", "1")]);
     assert!(config.validate().is_err());
 }
+
+/// Insert an indexer entry with the given status without running envio
+///
+/// Returns the entry's output directory, which is removed when dropped.
+#[must_use = "the seeded output directory is removed when dropped"]
+async fn seed_indexer(
+    context: &ServiceContext,
+    id: &str,
+    status: IndexerStatus,
+) -> tempfile::TempDir {
+    // A directory of its own, so a stop can never match unrelated processes
+    let output_dir = tempfile::Builder::new()
+        .prefix(&format!("seeded-{}-", id))
+        .tempdir()
+        .unwrap();
+    let process = IndexerProcess {
+        id: id.to_string(),
+        config: IndexerConfig::new(id.to_string(), vec![create_test_contract("Test", "1")]),
+        output_dir: output_dir.path().to_path_buf(),
+        process: None,
+        status,
+        logs: Vec::new(),
+        last_checked: std::time::Instant::now(),
+        progress: Default::default(),
+        health_failures: 0,
+        log_broadcast: None,
+        health_check: Default::default(),
+        attached_pid: None,
+        graphql_endpoint: None,
+        spawn_config: None,
+    };
+    context
+        .indexers
+        .write()
+        .await
+        .insert(id.to_string(), process);
+    output_dir
+}
+
+#[tokio::test]
+async fn test_health_summary_counts() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+
+    let _seeded = seed_indexer(&context, "running_1", IndexerStatus::Running).await;
+    let _seeded = seed_indexer(&context, "running_2", IndexerStatus::Running).await;
+    let _seeded = seed_indexer(&context, "starting", IndexerStatus::Starting).await;
+    let _seeded = seed_indexer(&context, "failed", IndexerStatus::Failed("boom".into())).await;
+    let _seeded = seed_indexer(&context, "stopped", IndexerStatus::Stopped).await;
+
+    let summary = context.health_summary().await;
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.running, 2);
+    assert_eq!(summary.starting, 1);
+    assert_eq!(summary.failed, 1);
+    assert_eq!(summary.stopped, 1);
+    assert!(matches!(
+        summary.indexers.get("failed"),
+        Some(IndexerStatus::Failed(reason)) if reason == "boom"
+    ));
+}
+
+#[tokio::test]
+async fn test_metrics_text() {
+    use crate::envio_utils::IndexerProgress;
+
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "metrics_test", IndexerStatus::Running).await;
+
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("metrics_test")
+        .unwrap()
+        .progress
+        .insert(
+            "1".to_string(),
+            IndexerProgress {
+                events_processed: Some(1234),
+                blocks_current: Some(100),
+                blocks_total: Some(200),
+                chain_id: Some("1".to_string()),
+                ..Default::default()
+            },
+        );
+
+    let metrics = context.metrics_text().await;
+    assert!(metrics.contains("# HELP envio_indexers "));
+    assert!(metrics.contains("# TYPE envio_indexers gauge"));
+    assert!(metrics.contains("# TYPE envio_indexer_events_processed gauge"));
+    assert!(metrics.contains("envio_indexers 1\n"));
+    assert!(metrics.contains(
+        "envio_indexer_events_processed{indexer_id=\"metrics_test\",chain_id=\"1\"} 1234"
+    ));
+    assert!(
+        metrics.contains("envio_indexer_status{indexer_id=\"metrics_test\",status=\"running\"} 1")
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires a running Docker daemon and the envio CLI"]
+async fn test_docker_indexer_start_stop() {
+    use crate::envio_utils::EnvioDockerConfig;
+    use crate::service_context::DeploymentMode;
+
+    let test_dir = std::env::temp_dir().join(format!("envio_docker_test_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&test_dir).unwrap();
+
+    let context = ServiceContext::new_docker(
+        GadgetConfiguration::default(),
+        test_dir,
+        EnvioDockerConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(context.deployment_mode, DeploymentMode::Docker);
+    let mut cleanup = TestCleanup::new(context.clone());
+
+    let config = IndexerConfig::new(
+        "docker_test".to_string(),
+        vec![create_test_contract("Greeter", "1")],
+    );
+    let result = context.spawn_indexer(config).await.unwrap();
+    cleanup.set_indexer_id(result.id.clone());
+
+    context.start_indexer(&result.id).await.unwrap();
+    let status = context.get_indexer_status(&result.id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Starting));
+    // Each container is published on a host port of its own
+    let port = context.indexers.read().await[&result.id].health_check.port;
+    let endpoint = context.graphql_endpoint(&result.id).await.unwrap().unwrap();
+    assert_eq!(endpoint, format!("http://localhost:{}/v1/graphql", port));
+
+    context.stop_indexer(&result.id).await.unwrap();
+    let status = context.get_indexer_status(&result.id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Stopped));
+}
+
+#[tokio::test]
+#[ignore = "requires the envio CLI and network access to a block explorer"]
+async fn test_spawn_inferred_indexer() {
+    use crate::test_utils::create_test_inferred_contract;
+
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let mut cleanup = TestCleanup::new(context.clone());
+
+    let config = IndexerConfig::new(
+        "inferred_test".to_string(),
+        vec![create_test_inferred_contract("USDC", "1")],
+    );
+    let params = SpawnIndexerParams::new(config);
+    let params_bytes = serde_json::to_vec(&params).unwrap();
+
+    let result = spawn_indexer_local(params_bytes, context).await.unwrap();
+    let result: SpawnIndexerResult = serde_json::from_slice(&result).unwrap();
+    cleanup.set_indexer_id(result.id.clone());
+
+    assert!(result.id.contains("inferred_test"));
+}
+
+#[tokio::test]
+async fn test_in_memory_logs_are_bounded() {
+    use crate::service_context::MAX_IN_MEMORY_LOG_LINES;
+
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let seeded = seed_indexer(&context, "log_test", IndexerStatus::Running).await;
+
+    let mut indexers = context.indexers.write().await;
+    let process = indexers.get_mut("log_test").unwrap();
+    for i in 0..MAX_IN_MEMORY_LOG_LINES + 500 {
+        process.push_log(format!("line {}", i));
+    }
+
+    assert_eq!(process.logs.len(), MAX_IN_MEMORY_LOG_LINES);
+    assert_eq!(process.logs[0], "line 500");
+    drop(indexers);
+
+    assert_eq!(
+        context.log_file_path("log_test").await.unwrap(),
+        seeded.path().join(crate::envio_utils::INDEXER_LOG_FILE)
+    );
+}
+
+#[test]
+fn test_log_dedup_window() {
+    use crate::service_context::LogDeduplicator;
+    use std::time::Instant;
+
+    let mut dedup = LogDeduplicator::new(Duration::from_secs(60), 2);
+    let start = Instant::now();
+
+    assert!(dedup.should_emit("Starting indexer", start));
+    assert!(!dedup.should_emit("Starting indexer", start + Duration::from_secs(30)));
+
+    // Re-emitted after the window has passed
+    assert!(dedup.should_emit("Starting indexer", start + Duration::from_secs(61)));
+
+    // Evicted once capacity is exceeded
+    assert!(dedup.should_emit("line a", start + Duration::from_secs(62)));
+    assert!(dedup.should_emit("line b", start + Duration::from_secs(63)));
+    assert!(dedup.should_emit("Starting indexer", start + Duration::from_secs(64)));
+}
+
+#[tokio::test]
+async fn test_idempotent_spawn_returns_existing_indexer() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let config = IndexerConfig::new(
+        "idempotent_test".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+
+    // Simulate the first delivery having already created the indexer
+    let id = ServiceContext::idempotent_indexer_id(&config.name, "call-42");
+    let _seeded = seed_indexer(&context, &id, IndexerStatus::Configured).await;
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut(&id)
+        .unwrap()
+        .spawn_config = Some(config.clone());
+
+    let first = context
+        .spawn_indexer_idempotent(config.clone(), "call-42")
+        .await
+        .unwrap();
+    let second = context
+        .spawn_indexer_idempotent(config.clone(), "call-42")
+        .await
+        .unwrap();
+
+    assert_eq!(first.id, id);
+    assert_eq!(second.id, id);
+    assert_eq!(context.list_indexers().await.len(), 1);
+
+    // The same addresses in another case are still the same config
+    let mut lowercased = config.clone();
+    lowercased.contracts[0].deployments[0].address =
+        "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string();
+    let third = context
+        .spawn_indexer_idempotent(lowercased, "call-42")
+        .await
+        .unwrap();
+    assert_eq!(third.id, id);
+
+    // Reusing the key for another config is a mistake, not a retry
+    let mut changed = config.clone();
+    changed.contracts.push(create_test_contract("Other", "1"));
+    let err = context
+        .spawn_indexer_idempotent(changed, "call-42")
+        .await
+        .unwrap_err();
+    assert!(err.contains("different config"));
+
+    // Keys that only differ in punctuation belong to different spawns
+    assert_ne!(
+        id,
+        ServiceContext::idempotent_indexer_id(&config.name, "call_42")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_idempotent_spawns_register_once() {
+    use crate::envio_utils::EnvioManager;
+    use crate::test_utils::write_stub_envio;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio(stub_dir.path());
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+    let config = IndexerConfig::new(
+        "concurrent".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+
+    let spawns: Vec<_> = (0..2)
+        .map(|_| {
+            let context = context.clone();
+            let config = config.clone();
+            tokio::spawn(async move { context.spawn_indexer_idempotent(config, "once").await })
+        })
+        .collect();
+    let mut messages = Vec::new();
+    for spawn in spawns {
+        messages.push(spawn.await.unwrap().unwrap().message);
+    }
+    messages.sort();
+
+    assert_eq!(
+        messages,
+        vec!["Indexer already exists", "Indexer spawned successfully"]
+    );
+    assert_eq!(context.list_indexers().await.len(), 1);
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_codegen_marks_indexer_failed() {
+    use crate::envio_utils::EnvioManager;
+    use crate::test_utils::write_stub_envio_with;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio_with(stub_dir.path(), "exit 1", "exec sleep 30");
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+    let config = IndexerConfig::new(
+        "codegen_failed".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+
+    let spawned = context
+        .spawn_indexer_idempotent(config, "retry")
+        .await
+        .unwrap();
+    assert!(context.start_indexer(&spawned.id).await.is_err());
+
+    // A retried job must not take the indexer for one that is starting
+    let status = context.get_indexer_status(&spawned.id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Failed(_)), "{:?}", status);
+    assert!(!context.has_live_instance(&spawned.id).await.unwrap());
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+async fn test_lookup_indexer_by_name() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+
+    // Seeded indexers use their id as the config name
+    let _seeded = seed_indexer(&context, "indexer_usdc_1", IndexerStatus::Running).await;
+    let _seeded = seed_indexer(&context, "indexer_weth_1", IndexerStatus::Running).await;
+
+    assert_eq!(
+        context.find_indexers_by_name("indexer_usdc_1").await,
+        vec!["indexer_usdc_1".to_string()]
+    );
+    assert_eq!(
+        context.get_by_name("indexer_weth_1").await.unwrap(),
+        "indexer_weth_1"
+    );
+    assert!(context.get_by_name("missing").await.is_err());
+}
+
+#[tokio::test]
+async fn test_health_check_recovery() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "health_test", IndexerStatus::Starting).await;
+
+    let mut indexers = context.indexers.write().await;
+    let process = indexers.get_mut("health_test").unwrap();
+
+    // Not being healthy yet is expected while starting up
+    for _ in 0..5 {
+        process.record_health_check(IndexerStatus::Starting, 3);
+    }
+    assert!(matches!(process.status, IndexerStatus::Starting));
+    assert_eq!(process.health_failures, 0);
+
+    process.record_health_check(IndexerStatus::Running, 3);
+    process.record_health_check(IndexerStatus::Starting, 3);
+    process.record_health_check(IndexerStatus::Starting, 3);
+    assert!(matches!(process.status, IndexerStatus::Running));
+    assert_eq!(process.health_failures, 2);
+    assert!(process.next_health_check_interval() > Duration::from_secs(5));
+
+    process.record_health_check(IndexerStatus::Running, 3);
+    assert!(matches!(process.status, IndexerStatus::Running));
+    assert_eq!(process.health_failures, 0);
+    assert_eq!(process.next_health_check_interval(), Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_health_check_failure_threshold() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "health_test", IndexerStatus::Running).await;
+
+    let mut indexers = context.indexers.write().await;
+    let process = indexers.get_mut("health_test").unwrap();
+
+    for _ in 0..2 {
+        process.record_health_check(IndexerStatus::Starting, 3);
+        assert!(!matches!(process.status, IndexerStatus::Failed(_)));
+    }
+    process.record_health_check(IndexerStatus::Starting, 3);
+    assert!(matches!(process.status, IndexerStatus::Failed(_)));
+
+    // An exited process fails immediately, unless it exited cleanly
+    process.status = IndexerStatus::Running;
+    process.health_failures = 0;
+    process.record_health_check(IndexerStatus::Failed("exit status 1".to_string()), 3);
+    assert!(matches!(process.status, IndexerStatus::Failed(_)));
+    process.status = IndexerStatus::Running;
+    process.record_health_check(IndexerStatus::Stopped, 3);
+    assert_eq!(process.status, IndexerStatus::Stopped);
+}
+
+#[tokio::test]
+async fn test_unhealthy_indexer_is_stopped_when_failed() {
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .base_dir(std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4())))
+        .max_health_failures(1)
+        .build()
+        .unwrap();
+    let _seeded = seed_indexer(&context, "unhealthy", IndexerStatus::Running).await;
+    let mut unhealthy = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    {
+        let mut indexers = context.indexers.write().await;
+        let process = indexers.get_mut("unhealthy").unwrap();
+        process.attached_pid = Some(unhealthy.id());
+        // Nothing serves the health endpoint on this port
+        process.health_check.port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        process.last_checked = std::time::Instant::now()
+            .checked_sub(Duration::from_secs(60))
+            .unwrap();
+    }
+
+    let status = context.monitor_indexer("unhealthy").await.unwrap();
+    assert!(matches!(status, IndexerStatus::Failed(_)));
+    assert!(unhealthy.try_wait().unwrap().is_some());
+    assert!(context.indexers.read().await["unhealthy"]
+        .logs
+        .iter()
+        .any(|line| line.contains("failed its health checks, stopped it")));
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test]
+async fn test_wait_for_running() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "wait_test", IndexerStatus::Starting).await;
+
+    // Simulate the indexer becoming healthy after a short delay
+    let indexers = context.indexers.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(700)).await;
+        indexers.write().await.get_mut("wait_test").unwrap().status = IndexerStatus::Running;
+    });
+
+    context
+        .wait_for_running("wait_test", Duration::from_secs(5))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_wait_for_running_failure_and_timeout() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "failed", IndexerStatus::Failed("crashed".into())).await;
+    let _seeded = seed_indexer(&context, "slow", IndexerStatus::Starting).await;
+
+    let err = context
+        .wait_for_running("failed", Duration::from_secs(5))
+        .await
+        .unwrap_err();
+    assert!(err.contains("crashed"));
+
+    let err = context
+        .wait_for_running("slow", Duration::from_millis(600))
+        .await
+        .unwrap_err();
+    assert!(err.contains("Timed out"));
+}
+
+#[test]
+fn test_spawn_params_versions() {
+    use crate::service_context::SPAWN_PARAMS_VERSION;
+
+    let config = IndexerConfig::new(
+        "versioned".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+
+    // v0: no version field
+    let v0 = serde_json::json!({ "config": config });
+    let params = SpawnIndexerParams::from_slice(&serde_json::to_vec(&v0).unwrap()).unwrap();
+    assert_eq!(params.version, SPAWN_PARAMS_VERSION);
+    assert_eq!(params.config.name, "versioned");
+    assert!(params.idempotency_key.is_none());
+
+    // v0: bare config
+    let bare = serde_json::to_vec(&config).unwrap();
+    let params = SpawnIndexerParams::from_slice(&bare).unwrap();
+    assert_eq!(params.config.contracts.len(), 1);
+
+    // Current version
+    let current = serde_json::to_vec(&SpawnIndexerParams::new(config.clone())).unwrap();
+    let params = SpawnIndexerParams::from_slice(&current).unwrap();
+    assert_eq!(params.version, SPAWN_PARAMS_VERSION);
+
+    // Future version
+    let future = serde_json::json!({ "version": SPAWN_PARAMS_VERSION + 1, "config": config });
+    let err = SpawnIndexerParams::from_slice(&serde_json::to_vec(&future).unwrap()).unwrap_err();
+    assert!(err.contains("Unsupported params version"));
+}
+
+#[test]
+fn test_context_base_dir() {
+    let err = ServiceContext::new(GadgetConfiguration::default(), PathBuf::new())
+        .err()
+        .expect("Empty base dir should be rejected");
+    assert!(err.contains("data_dir"));
+
+    let err = ServiceContext::new(GadgetConfiguration::default(), PathBuf::from("indexers"))
+        .err()
+        .expect("Relative base dir should be rejected");
+    assert!(err.contains("absolute"));
+}
+
+#[tokio::test]
+async fn test_context_ipfs_gateway() {
+    use crate::envio_utils::ContractSource;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/ipfs/QmGreeter"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(test_utils::GREETER_ABI))
+        .mount(&server)
+        .await;
+
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .base_dir(std::env::temp_dir())
+        .ipfs_gateway(format!("{}/ipfs/", server.uri()))
+        .build()
+        .unwrap();
+    let mut contract = create_test_contract("Greeter", "1");
+    contract.source = ContractSource::Abi {
+        abi: None,
+        url: Some("ipfs://QmGreeter".to_string()),
+        path: None,
+    };
+    assert_eq!(
+        context.envio_manager.get_abi(&contract).await.unwrap(),
+        test_utils::GREETER_ABI
+    );
+}
+
+#[tokio::test]
+async fn test_jobs_share_context_state() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "shared", IndexerStatus::Running).await;
+
+    // Handlers receive clones of the context, as registered with the runner
+    let result = stop_indexer_local(b"shared".to_vec(), context.clone())
+        .await
+        .expect("Job should reach the shared indexer");
+    assert_eq!(
+        String::from_utf8(result).unwrap(),
+        "Successfully stopped indexer shared"
+    );
+
+    let status = context.get_indexer_status("shared").await.unwrap();
+    assert!(matches!(status, IndexerStatus::Stopped));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spawn_reuses_existing_project() {
+    use crate::envio_utils::EnvioManager;
+    use crate::test_utils::write_stub_envio;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio(stub_dir.path());
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    // A fresh context over the same base directory, like the service after a restart
+    let restarted = || {
+        ServiceContext::builder(GadgetConfiguration::default())
+            .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+            .build()
+            .unwrap()
+    };
+    let config = IndexerConfig::new(
+        "resumed".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let id = ServiceContext::idempotent_indexer_id(&config.name, "retry");
+
+    let result = restarted()
+        .spawn_indexer_idempotent(config.clone(), "retry")
+        .await
+        .unwrap();
+    assert_eq!(result.message, "Indexer spawned successfully");
+    assert!(restarted().envio_manager.project_exists(&id));
+
+    // The project initialized before the restart is picked up again
+    let context = restarted();
+    let result = context
+        .spawn_indexer_idempotent(config.clone(), "retry")
+        .await
+        .expect("Spawn should reuse the existing project");
+    assert_eq!(result.id, id);
+    assert_eq!(result.message, "Indexer spawned from existing project");
+    let status = context.get_indexer_status(&id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Configured));
+
+    // A project created for another config is initialized again
+    let mut changed = config;
+    changed.contracts.push(create_test_contract("Other", "1"));
+    let result = restarted()
+        .spawn_indexer_idempotent(changed, "retry")
+        .await
+        .unwrap();
+    assert_eq!(result.message, "Indexer spawned successfully");
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires the envio CLI"]
+async fn test_spawn_and_start_rolls_back_failed_start() {
+    use crate::envio_utils::{DockerConnection, EnvioDockerConfig};
+
+    let data_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&data_dir).unwrap();
+
+    // Nothing listens on this endpoint, so starting the container fails
+    let docker_config = EnvioDockerConfig {
+        connection: DockerConnection::Http("http://127.0.0.1:1".to_string()),
+        ..Default::default()
+    };
+    let context = ServiceContext::new_docker(
+        GadgetConfiguration::default(),
+        data_dir.clone(),
+        docker_config,
+    )
+    .unwrap();
+
+    let config = IndexerConfig::new(
+        "rollback".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let result = context
+        .spawn_and_start(config, Some(Duration::from_secs(5)))
+        .await;
+
+    assert!(result.is_err());
+    assert!(context.list_indexers().await.is_empty());
+    assert_eq!(std::fs::read_dir(&data_dir).unwrap().count(), 0);
+
+    std::fs::remove_dir_all(&data_dir).ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spawn_and_start_rolls_back_failed_codegen() {
+    use crate::envio_utils::EnvioManager;
+    use crate::test_utils::write_stub_envio_with;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio_with(stub_dir.path(), "exit 1", "exec sleep 30");
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+
+    let config = IndexerConfig::new(
+        "rollback".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let result = context
+        .spawn_and_start(config, Some(Duration::from_secs(5)))
+        .await;
+
+    // The indexer was initialized, so the start is what gets rolled back
+    assert!(result.is_err());
+    assert!(context.list_indexers().await.is_empty());
+    assert_eq!(std::fs::read_dir(&base_dir).unwrap().count(), 0);
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+async fn test_cancel_spawn_without_pending_spawn() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    assert!(context.cancel_spawn("missing").await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires the envio CLI"]
+async fn test_cancel_spawn_mid_init() {
+    let context = ServiceContext::new_test().await;
+    let config = IndexerConfig::new(
+        "cancelled".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let id = ServiceContext::idempotent_indexer_id(&config.name, "cancel");
+
+    let spawn_context = context.clone();
+    let spawn = tokio::spawn(async move {
+        spawn_context
+            .spawn_indexer_idempotent(config, "cancel")
+            .await
+    });
+
+    // Wait until init has registered its token, then cancel it
+    let deadline = std::time::Instant::now() + Duration::from_secs(30);
+    while context.cancel_spawn(&id).await.is_err() {
+        assert!(std::time::Instant::now() < deadline, "Spawn never started");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let result = spawn.await.unwrap();
+    assert!(result.is_err());
+    assert!(!context.envio_manager.base_dir().join(&id).exists());
+    assert!(context.list_indexers().await.is_empty());
+
+    let ps = std::process::Command::new("pgrep")
+        .args(["-f", "envio init"])
+        .output()
+        .unwrap();
+    assert!(ps.stdout.is_empty(), "envio init is still running");
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancel_spawn_mid_codegen() {
+    use crate::envio_utils::{EnvioManager, SpawnProgress};
+    use crate::test_utils::write_stub_envio_with;
+
+    // Like the stub envio, but codegen hangs until it is killed
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let codegen_pid = stub_dir.path().join("codegen.pid");
+    let stub = write_stub_envio_with(
+        stub_dir.path(),
+        &format!("echo $$ > {}; exec sleep 30", codegen_pid.display()),
+        "exec sleep 30",
+    );
+
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+    let mut events = context.envio_manager.stream_init_progress();
+
+    let config = IndexerConfig::new(
+        "codegen_cancelled".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let spawn_context = context.clone();
+    let spawn = tokio::spawn(async move { spawn_context.spawn_and_start(config, None).await });
+
+    let id = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let event = events.recv().await.unwrap();
+            if event.progress == SpawnProgress::RunningCodegen {
+                break event.id;
+            }
+        }
+    })
+    .await
+    .expect("Spawn never reached codegen");
+    context.cancel_spawn(&id).await.unwrap();
+
+    let result = tokio::time::timeout(Duration::from_secs(10), spawn)
+        .await
+        .expect("Cancelled spawn should return promptly")
+        .unwrap();
+    assert!(result.is_err());
+    assert!(!base_dir.join(&id).exists());
+    assert!(context.list_indexers().await.is_empty());
+
+    let pid: u32 = std::fs::read_to_string(&codegen_pid)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap();
+    assert!(
+        !crate::envio_utils::project::process_alive(pid).await,
+        "envio codegen is still running"
+    );
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[test]
+fn test_progress_tracked_per_chain() {
+    use crate::envio_utils::project::IndexerProgress;
+    use crate::service_context::ProgressTracker;
+
+    let progress = |chain_id: &str, events: usize, percentage: usize| IndexerProgress {
+        events_processed: Some(events),
+        chain_id: Some(chain_id.to_string()),
+        percentage: Some(percentage),
+        ..Default::default()
+    };
+
+    let mut tracker = ProgressTracker::default();
+    let now = std::time::Instant::now();
+    assert_eq!(tracker.record(progress("1", 100, 10), now).len(), 1);
+
+    // A second chain adds an aggregate summary
+    let summaries = tracker.record(progress("10", 50, 40), now);
+    assert_eq!(summaries.len(), 2);
+    assert!(summaries[0].contains("Chain: 10"));
+    assert!(summaries[1].contains("All 2 chains: Events: 150, 10%"));
+
+    tracker.record(progress("1", 300, 60), now);
+    tracker.record(progress("10", 80, 45), now);
+
+    assert_eq!(tracker.get("1").unwrap().events_processed, Some(300));
+    assert_eq!(tracker.get("10").unwrap().events_processed, Some(80));
+    assert_eq!(tracker.total_events(), 380);
+    assert_eq!(tracker.min_percentage(), Some(45));
+}
+
+#[tokio::test]
+async fn test_export_config_round_trip() {
+    use crate::envio_utils::ConfigFormat;
+
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let _seeded = seed_indexer(&context, "exported", IndexerStatus::Running).await;
+    let config = context.get_indexer_config("exported").await.unwrap();
+
+    for format in [ConfigFormat::Json, ConfigFormat::Yaml] {
+        let exported = context.export_config("exported", format).await.unwrap();
+        let imported = IndexerConfig::import(&exported, format).unwrap();
+        assert_eq!(imported, config, "{:?} export should round-trip", format);
+    }
+
+    assert!(context
+        .export_config("missing", ConfigFormat::Json)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_import_project() {
+    let context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+
+    let err = context
+        .import_project(project.path().to_path_buf(), "imported".to_string())
+        .await
+        .unwrap_err();
+    assert!(err.contains("config.yaml"));
+
+    std::fs::write(
+        project.path().join("config.yaml"),
+        "name: imported\nnetworks:\n  - id: 1\n    start_block: 0\n    contracts:\n      - name: Token\n        address: 0x1111111111111111111111111111111111111111\n",
+    )
+    .unwrap();
+    let result = context
+        .import_project(project.path().to_path_buf(), "imported".to_string())
+        .await
+        .unwrap();
+
+    assert!(context.list_indexers().await.contains(&result.id));
+    let status = context.get_indexer_status(&result.id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Configured));
+    let config = context.get_indexer_config(&result.id).await.unwrap();
+    assert_eq!(config.name, "imported");
+    assert_eq!(config.contracts[0].name, "Token");
+    assert_eq!(config.contracts[0].deployments[0].network_id, "1");
+}
+
+#[tokio::test]
+async fn test_tail_logs_fans_out() {
+    use crate::envio_utils::IndexerLogMessage;
+
+    let mut context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    context.log_rotation = None;
+    let _seeded = seed_indexer(&context, "tailed", IndexerStatus::Running).await;
+
+    let child = tokio::process::Command::new("sh")
+        .args(["-c", "echo one; echo two"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("tailed")
+        .unwrap()
+        .process = Some(child);
+
+    async fn collect(
+        (snapshot, mut rx): (
+            Vec<IndexerLogMessage>,
+            tokio::sync::broadcast::Receiver<IndexerLogMessage>,
+        ),
+    ) -> Vec<String> {
+        let mut messages = snapshot;
+        while messages
+            .iter()
+            .filter(|msg| matches!(msg, IndexerLogMessage::Stdout(_)))
+            .count()
+            < 2
+        {
+            let msg = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("Timed out waiting for logs")
+                .expect("Log stream closed");
+            messages.push(msg);
+        }
+        messages
+            .into_iter()
+            .filter_map(|msg| match msg {
+                IndexerLogMessage::Stdout(line) => Some(line),
+                _ => None,
+            })
+            .collect()
+    }
+
+    let first = context.tail_logs("tailed", 10).await.unwrap();
+    let second = context.tail_logs("tailed", 10).await.unwrap();
+
+    let expected = vec!["one".to_string(), "two".to_string()];
+    assert_eq!(collect(first).await, expected);
+    assert_eq!(collect(second).await, expected);
+}
+
+#[tokio::test]
+async fn test_tail_logs_after_stream_ended() {
+    use crate::envio_utils::IndexerLogMessage;
+
+    let context = ServiceContext::new_test().await;
+    let _seeded = seed_indexer(&context, "finished", IndexerStatus::Running).await;
+
+    let child = tokio::process::Command::new("sh")
+        .args(["-c", "echo one; echo two; echo three"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("finished")
+        .unwrap()
+        .process = Some(child);
+
+    context.tail_logs("finished", 10).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while context.indexers.read().await["finished"]
+            .log_broadcast
+            .as_ref()
+            .is_some_and(|b| b.is_active())
+        {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("Log stream should end with the process");
+
+    // Tailing a finished indexer reads what was persisted instead of reopening its output
+    for _ in 0..2 {
+        let (tail, mut live) = context.tail_logs("finished", 2).await.unwrap();
+        let lines: Vec<String> = tail
+            .into_iter()
+            .filter_map(|msg| match msg {
+                IndexerLogMessage::Stdout(line) => Some(line),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lines, vec!["two", "three"]);
+        assert!(matches!(
+            live.recv().await,
+            Err(tokio::sync::broadcast::error::RecvError::Closed)
+        ));
+    }
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test]
+async fn test_subscribe_to_logs_twice() {
+    use crate::envio_utils::IndexerLogMessage;
+
+    let mut context =
+        ServiceContext::new(GadgetConfiguration::default(), std::env::temp_dir()).unwrap();
+    context.log_rotation = None;
+    let _seeded = seed_indexer(&context, "resubscribed", IndexerStatus::Running).await;
+
+    let child = tokio::process::Command::new("sh")
+        .args(["-c", "echo first; sleep 1; echo second"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("resubscribed")
+        .unwrap()
+        .process = Some(child);
+
+    async fn next_stdout(rx: &mut tokio::sync::mpsc::Receiver<IndexerLogMessage>) -> String {
+        loop {
+            let msg = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("Timed out waiting for logs")
+                .expect("Log stream closed");
+            if let IndexerLogMessage::Stdout(line) = msg {
+                return line;
+            }
+        }
+    }
+
+    let mut first = context
+        .subscribe_to_indexer_logs("resubscribed")
+        .await
+        .unwrap();
+    assert_eq!(next_stdout(&mut first).await, "first");
+    drop(first);
+
+    // The second subscription replays buffered lines and keeps receiving new ones
+    let mut second = context
+        .subscribe_to_indexer_logs("resubscribed")
+        .await
+        .unwrap();
+    assert_eq!(next_stdout(&mut second).await, "first");
+    assert_eq!(next_stdout(&mut second).await, "second");
+}
+
+#[tokio::test]
+async fn test_spawn_reports_warnings() {
+    let context = ServiceContext::new_test().await;
+    let config = IndexerConfig::new(
+        "warned".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    assert!(config.contracts[0].deployments[0].start_block.is_none());
+
+    // A pre-existing project lets the spawn succeed without the envio CLI
+    let id = ServiceContext::idempotent_indexer_id(&config.name, "warn");
+    let project_dir = context.envio_manager.base_dir().join(&id);
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("config.yaml"), "name: warned\n").unwrap();
+
+    let result = context
+        .spawn_indexer_idempotent(config, "warn")
+        .await
+        .expect("A missing start block should not fail the spawn");
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.contains("has no start block")));
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spawn_and_start_reports_progress() {
+    use crate::envio_utils::{EnvioManager, SpawnProgress};
+    use crate::test_utils::write_stub_envio;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio(stub_dir.path());
+
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+
+    let config = IndexerConfig::new(
+        "progress".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel();
+    let result = context
+        .spawn_and_start_with_progress(config, None, progress_tx)
+        .await
+        .expect("Spawn with the stubbed CLI should succeed");
+
+    let mut stages = Vec::new();
+    while let Ok(stage) = progress_rx.try_recv() {
+        stages.push(stage);
+    }
+    assert_eq!(
+        stages,
+        vec![
+            SpawnProgress::FetchingAbi("Test".to_string()),
+            SpawnProgress::RunningInit,
+            SpawnProgress::InitComplete,
+            SpawnProgress::RunningCodegen,
+            SpawnProgress::Starting,
+        ]
+    );
+
+    context.stop_indexer(&result.id).await.unwrap();
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+async fn test_spawn_emits_info_log() {
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+
+    struct LevelRecorder(Arc<Mutex<Vec<tracing::Level>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for LevelRecorder {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            self.0.lock().unwrap().push(*event.metadata().level());
+        }
+    }
+
+    // The current-thread test runtime keeps every event on this thread's default subscriber
+    let levels = Arc::new(Mutex::new(Vec::new()));
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(LevelRecorder(levels.clone())),
+    );
+
+    let context = ServiceContext::new_test().await;
+    let config = IndexerConfig::new(
+        "logged".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let id = ServiceContext::idempotent_indexer_id(&config.name, "log");
+    let project_dir = context.envio_manager.base_dir().join(&id);
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("config.yaml"), "name: logged\n").unwrap();
+
+    context
+        .spawn_indexer_idempotent(config, "log")
+        .await
+        .expect("Spawn should reuse the existing project");
+    assert!(levels.lock().unwrap().contains(&tracing::Level::INFO));
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[test]
+fn test_builder_docker_context() {
+    use crate::envio_utils::EnvioDockerConfig;
+    use crate::service_context::DeploymentMode;
+
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .base_dir(&base_dir)
+        .docker(EnvioDockerConfig::default())
+        .max_health_failures(3)
+        .log_rotation(None)
+        .build()
+        .expect("Docker context should build without contacting the daemon");
+
+    assert_eq!(context.deployment_mode, DeploymentMode::Docker);
+    assert!(context.envio_docker.is_some());
+    assert_eq!(context.envio_manager.base_dir(), base_dir);
+    assert_eq!(context.max_health_failures, 3);
+    assert!(context.log_rotation.is_none());
+
+    // Without a base directory or manager there is nowhere to put projects
+    assert!(ServiceContext::builder(GadgetConfiguration::default())
+        .build()
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_indexer_id_skips_taken_ids() {
+    let context = ServiceContext::new_test().await;
+
+    // One candidate is registered, the next already has a project directory
+    let _seeded = seed_indexer(&context, "indexer_dup_a", IndexerStatus::Running).await;
+    std::fs::create_dir_all(context.envio_manager.base_dir().join("indexer_dup_b")).unwrap();
+
+    let mut suffixes = ["a", "b", "c"].into_iter().map(str::to_string);
+    let indexers = context.indexers.read().await;
+    let id = context.unique_indexer_id(&indexers, "dup", || suffixes.next().unwrap());
+    assert_eq!(id, "indexer_dup_c");
+    drop(indexers);
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[test]
+fn test_anvil_indexer_config_is_valid() {
+    let config = crate::test_utils::create_anvil_indexer();
+    assert!(config.validate().is_ok());
+    assert_eq!(config.contracts[0].deployments[0].network_id, "31337");
+}
+
+#[tokio::test]
+#[ignore = "requires the envio CLI and an Anvil node on localhost:8545"]
+async fn test_spawn_anvil_indexer() {
+    let context = ServiceContext::new_test().await;
+    let mut cleanup = TestCleanup::new(context.clone());
+
+    let result = context
+        .spawn_and_start(
+            crate::test_utils::create_anvil_indexer(),
+            Some(Duration::from_secs(60)),
+        )
+        .await
+        .expect("Indexer should start against the local Anvil node");
+    cleanup.set_indexer_id(result.id.clone());
+
+    let status = context.get_indexer_status(&result.id).await.unwrap();
+    assert!(matches!(status, IndexerStatus::Running));
+}
+
+#[tokio::test]
+async fn test_prune_stopped() {
+    let context = ServiceContext::new_test().await;
+    let base_dir = context.envio_manager.base_dir().to_path_buf();
+    let hour_ago = std::time::Instant::now()
+        .checked_sub(Duration::from_secs(3600))
+        .unwrap();
+
+    for (id, status) in [
+        ("stale_stopped", IndexerStatus::Stopped),
+        ("stale_running", IndexerStatus::Running),
+        (
+            "stale_failed",
+            IndexerStatus::Failed("unhealthy".to_string()),
+        ),
+        ("fresh_failed", IndexerStatus::Failed("crashed".to_string())),
+    ] {
+        let output_dir = base_dir.join(id);
+        std::fs::create_dir_all(&output_dir).unwrap();
+        let _seeded = seed_indexer(&context, id, status).await;
+        let mut indexers = context.indexers.write().await;
+        let process = indexers.get_mut(id).unwrap();
+        process.output_dir = output_dir;
+        if id.starts_with("stale") {
+            process.last_checked = hour_ago;
+        }
+    }
+
+    // A failed indexer whose process is still running
+    let mut survivor = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("stale_failed")
+        .unwrap()
+        .attached_pid = Some(survivor.id());
+
+    let mut pruned = context
+        .prune_stopped(Duration::from_secs(600))
+        .await
+        .unwrap();
+    pruned.sort();
+    assert_eq!(pruned, vec!["stale_failed", "stale_stopped"]);
+    assert!(!base_dir.join("stale_stopped").exists());
+    assert!(!base_dir.join("stale_failed").exists());
+    // Pruning stopped it rather than leaving it orphaned
+    assert!(survivor.try_wait().unwrap().is_some());
+    assert!(base_dir.join("stale_running").exists());
+    assert!(base_dir.join("fresh_failed").exists());
+
+    let mut remaining = context.list_indexers().await;
+    remaining.sort();
+    assert_eq!(remaining, vec!["fresh_failed", "stale_running"]);
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+async fn test_custom_health_check_endpoint() {
+    use crate::envio_utils::HealthCheckConfig;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/healthz"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let context = ServiceContext::new_test().await;
+    let _seeded = seed_indexer(&context, "custom_health", IndexerStatus::Starting).await;
+    context
+        .set_health_check(
+            "custom_health",
+            HealthCheckConfig {
+                port: server.address().port(),
+                path: "/healthz".to_string(),
+                expected_status: 200,
+            },
+        )
+        .await
+        .unwrap();
+    {
+        // A live process and an overdue check make monitoring hit the endpoint
+        let mut indexers = context.indexers.write().await;
+        let process = indexers.get_mut("custom_health").unwrap();
+        process.process = Some(
+            tokio::process::Command::new("sleep")
+                .arg("30")
+                .kill_on_drop(true)
+                .spawn()
+                .unwrap(),
+        );
+        process.last_checked = std::time::Instant::now()
+            .checked_sub(Duration::from_secs(60))
+            .unwrap();
+    }
+
+    let status = context.monitor_indexer("custom_health").await.unwrap();
+    assert!(matches!(status, IndexerStatus::Running));
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test]
+async fn test_dry_run_generates_config_and_schema() {
+    let context = ServiceContext::new_test().await;
+    let mut contract = create_test_contract("Greeter", "1");
+    contract.deployments[0].start_block = Some(100);
+    contract.deployments[0].address = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string();
+    // Repeating a deployment doesn't list its address twice
+    contract.deployments.push(contract.deployments[0].clone());
+    let config = IndexerConfig::new("dry_run".to_string(), vec![contract]);
+
+    let report = context.dry_run(config).await.unwrap();
+    assert!(report.warnings.is_empty());
+
+    let envio_config: serde_yaml::Value = serde_yaml::from_str(&report.config_yaml).unwrap();
+    assert_eq!(envio_config["name"].as_str(), Some("dry_run"));
+    let events: Vec<&str> = envio_config["contracts"][0]["events"]
+        .as_sequence()
+        .unwrap()
+        .iter()
+        .filter_map(|event| event["event"].as_str())
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            "ClearGreeting(address user)",
+            "NewGreeting(address user, string greeting)"
+        ]
+    );
+    let network = &envio_config["networks"][0];
+    assert_eq!(network["id"].as_u64(), Some(1));
+    assert_eq!(network["start_block"].as_u64(), Some(100));
+    assert_eq!(
+        network["contracts"][0]["address"][0].as_str(),
+        Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")
+    );
+    assert_eq!(
+        network["contracts"][0]["address"]
+            .as_sequence()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert!(report.schema.contains("type Greeter_NewGreeting {"));
+    assert!(report.schema.contains("greeting: String!"));
+
+    // Nothing is spawned
+    assert!(context.list_indexers().await.is_empty());
+    assert!(std::fs::read_dir(context.envio_manager.base_dir())
+        .unwrap()
+        .next()
+        .is_none());
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test]
+async fn test_read_only_base_dir_is_rejected() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let base_dir = temp_dir.path().join("indexers");
+    std::fs::create_dir(&base_dir).unwrap();
+    std::fs::set_permissions(&base_dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+    // Privileged users write regardless of permissions, there is nothing to check then
+    if std::fs::write(base_dir.join("probe"), b"").is_ok() {
+        return;
+    }
+
+    let error = ServiceContext::new(GadgetConfiguration::default(), base_dir.clone())
+        .err()
+        .expect("A read-only base directory should be rejected");
+    assert!(error.contains(&base_dir.display().to_string()));
+    assert!(error.contains("not writable"));
+
+    // Skipping the check defers the error to the first spawn
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .base_dir(&base_dir)
+        .check_writable(false)
+        .build()
+        .unwrap();
+    assert!(context.check_writable().is_err());
+
+    std::fs::set_permissions(&base_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[tokio::test]
+async fn test_attach_and_stop_existing_process() {
+    let context = ServiceContext::new_test().await;
+    let _seeded = seed_indexer(&context, "recovered", IndexerStatus::Configured).await;
+    let project_dir = context.envio_manager.base_dir().join("recovered");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("recovered")
+        .unwrap()
+        .output_dir = project_dir.clone();
+
+    // Stands in for an `envio dev` left running by an earlier blueprint process
+    let mut survivor = tokio::process::Command::new("sleep")
+        .arg("30")
+        .current_dir(&project_dir)
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let pid = survivor.id().unwrap();
+
+    // A live process outside the project directory is not taken for the indexer
+    let stranger = tokio::process::Command::new("sleep")
+        .arg("30")
+        .current_dir(std::env::temp_dir())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let error = context
+        .attach_existing_process("recovered", stranger.id().unwrap())
+        .await
+        .unwrap_err();
+    assert!(error.contains("does not run in the project directory"));
+
+    context
+        .attach_existing_process("recovered", pid)
+        .await
+        .unwrap();
+    assert_eq!(
+        context.get_indexer_pid("recovered").await.unwrap(),
+        Some(pid)
+    );
+
+    context.stop_indexer("recovered").await.unwrap();
+    let exited = tokio::time::timeout(Duration::from_secs(5), survivor.wait())
+        .await
+        .expect("The attached process should have been stopped")
+        .unwrap();
+    assert!(!exited.success());
+    assert_eq!(
+        context.get_indexer_status("recovered").await.unwrap(),
+        IndexerStatus::Stopped
+    );
+    assert_eq!(context.get_indexer_pid("recovered").await.unwrap(), None);
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_status_transition_events() {
+    use crate::envio_utils::EnvioManager;
+    use crate::test_utils::write_stub_envio;
+
+    let stub_dir = tempfile::TempDir::new().unwrap();
+    let stub = write_stub_envio(stub_dir.path());
+
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(EnvioManager::new(base_dir.clone()).with_envio_binary(&stub))
+        .build()
+        .unwrap();
+    let mut events = context.subscribe_events();
+
+    let config = IndexerConfig::new(
+        "events".to_string(),
+        vec![create_test_contract("Test", "1")],
+    );
+    let spawned = context.spawn_indexer(config).await.unwrap();
+    context.start_indexer(&spawned.id).await.unwrap();
+    context.stop_indexer(&spawned.id).await.unwrap();
+
+    let mut transitions = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        assert_eq!(event.id, spawned.id);
+        transitions.push((event.old_status, event.new_status));
+    }
+    assert_eq!(
+        transitions,
+        vec![
+            (IndexerStatus::Configured, IndexerStatus::Starting),
+            (IndexerStatus::Starting, IndexerStatus::Stopped),
+        ]
+    );
+
+    std::fs::remove_dir_all(base_dir).ok();
+}
+
+#[tokio::test]
+async fn test_graphql_endpoint_from_logs() {
+    let context = ServiceContext::new_test().await;
+    let _seeded = seed_indexer(&context, "announced", IndexerStatus::Running).await;
+    assert_eq!(
+        context.graphql_endpoint("announced").await.unwrap(),
+        Some("http://localhost:8080/v1/graphql".to_string())
+    );
+
+    let child = tokio::process::Command::new("sh")
+        .args([
+            "-c",
+            "echo 'GraphQL: http://localhost:9191/v1/graphql'; sleep 5",
+        ])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("announced")
+        .unwrap()
+        .process = Some(child);
+
+    // Picked up by the pipeline reading the output, without any filtered subscriber
+    context.tail_logs("announced", 10).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while context.indexers.read().await["announced"]
+            .graphql_endpoint
+            .is_none()
+        {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("The announced endpoint should be recorded");
+    assert_eq!(
+        context.graphql_endpoint("announced").await.unwrap(),
+        Some("http://localhost:9191/v1/graphql".to_string())
+    );
+    assert_eq!(
+        context.indexers.read().await["announced"].health_check.port,
+        9191
+    );
+
+    std::fs::remove_dir_all(context.envio_manager.base_dir()).ok();
+}
+
+#[tokio::test]
+async fn test_stop_reports_surviving_process() {
+    use crate::envio_utils::EnvioManager;
+    use std::os::unix::fs::PermissionsExt;
+
+    let base_dir = std::env::temp_dir().join(format!("envio_test_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&base_dir).unwrap();
+    // A `kill` that refuses every signal stands in for a process that can't be killed
+    let refusing_kill = base_dir.join("kill");
+    std::fs::write(&refusing_kill, "#!/bin/sh\nexit 1\n").unwrap();
+    std::fs::set_permissions(&refusing_kill, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let mut survivor = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    let pid = survivor.id();
+
+    let context = ServiceContext::builder(GadgetConfiguration::default())
+        .envio_manager(
+            EnvioManager::new(base_dir.clone())
+                .with_stop_timeout(Duration::from_millis(200))
+                .with_kill_binary(&refusing_kill),
+        )
+        .build()
+        .unwrap();
+    let _seeded = seed_indexer(&context, "unkillable", IndexerStatus::Running).await;
+    context
+        .indexers
+        .write()
+        .await
+        .get_mut("unkillable")
+        .unwrap()
+        .attached_pid = Some(pid);
+
+    let error = context.stop_indexer("unkillable").await.unwrap_err();
+    assert!(error.contains(&format!("still running as process {}", pid)));
+    assert_eq!(
+        context.get_indexer_status("unkillable").await.unwrap(),
+        IndexerStatus::Running
+    );
+    assert_eq!(
+        context.get_indexer_pid("unkillable").await.unwrap(),
+        Some(pid)
+    );
+
+    survivor.kill().unwrap();
+    survivor.wait().unwrap();
+    std::fs::remove_dir_all(base_dir).ok();
+}